@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{read_to_string, write};
+use std::io::ErrorKind;
+use std::path::Path;
+
+// Expected answers, identified by day, part and a hash of the input they belong to.
+// On disk, every line has the form "<day> <part> <input hash> <expected answer>", lines starting
+// with '#' are comments.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct Answers {
+    expected: BTreeMap<(u8, u8, u64), String>,
+}
+
+impl Answers {
+    pub fn parse(input: &str) -> Result<Answers, String> {
+        let mut expected = BTreeMap::new();
+        for (i, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.splitn(4, ' ');
+            let (Some(day), Some(part), Some(hash), Some(answer)) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                return Err(format!("expected four fields in line {}: '{line}'", i + 1));
+            };
+            let day = day
+                .parse::<u8>()
+                .map_err(|e| format!("unable to parse day '{day}' in line {}: {e}", i + 1))?;
+            let part = part
+                .parse::<u8>()
+                .map_err(|e| format!("unable to parse part '{part}' in line {}: {e}", i + 1))?;
+            let hash = u64::from_str_radix(hash, 16).map_err(|e| {
+                format!("unable to parse input hash '{hash}' in line {}: {e}", i + 1)
+            })?;
+            expected.insert((day, part, hash), answer.trim().to_owned());
+        }
+        Ok(Answers { expected })
+    }
+
+    // a missing answers file is not an error, there are just no expected answers yet
+    pub fn load(path: &Path) -> Result<Answers, String> {
+        match read_to_string(path) {
+            Ok(content) => Answers::parse(&content)
+                .map_err(|e| format!("invalid answers file '{}': {e}", path.display())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(format!(
+                "unable to read answers file '{}': {e}",
+                path.display()
+            )),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        write(path, self.to_string())
+            .map_err(|e| format!("unable to write answers file '{}': {e}", path.display()))
+    }
+
+    pub fn get(&self, day: u8, part: u8, hash: u64) -> Option<&str> {
+        self.expected.get(&(day, part, hash)).map(|s| s.as_str())
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, hash: u64, answer: String) {
+        self.expected.insert((day, part, hash), answer);
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        writeln!(f, "# day part input-hash expected-answer")?;
+        for ((day, part, hash), answer) in &self.expected {
+            writeln!(f, "{day} {part} {hash:016x} {answer}")?;
+        }
+        Ok(())
+    }
+}
+
+// FNV-1a, because the hash must stay the same across rust versions and platforms
+pub fn input_hash(input: &str) -> u64 {
+    input.bytes().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const ANSWERS: &str = r#"# day part input-hash expected-answer
+1 1 00000000000000ff 142
+17 2 af63dc4c8601ec8c 94
+"#;
+
+    #[test]
+    fn parse_works_for_example() {
+        // given
+        let answers = Answers::parse(ANSWERS).expect("expected successful parsing");
+
+        // when
+        let part_one = answers.get(1, 1, 0xff);
+        let part_two = answers.get(17, 2, 0xaf63dc4c8601ec8c);
+        let missing = answers.get(17, 1, 0xaf63dc4c8601ec8c);
+
+        // then
+        assert_eq!(part_one, Some("142"));
+        assert_eq!(part_two, Some("94"));
+        assert_eq!(missing, None);
+    }
+
+    #[test]
+    fn display_is_inverse_of_parse() {
+        // given
+        let answers = Answers::parse(ANSWERS).expect("expected successful parsing");
+
+        // when
+        let written = answers.to_string();
+
+        // then
+        assert_eq!(written, ANSWERS);
+    }
+
+    #[test]
+    fn parse_fails_for_incomplete_lines() {
+        assert!(Answers::parse("1 1 00000000000000ff\n").is_err());
+        assert!(Answers::parse("1 x 00000000000000ff 142\n").is_err());
+    }
+
+    #[test]
+    fn input_hash_works() {
+        assert_eq!(input_hash(""), 0xcbf29ce484222325);
+        assert_eq!(input_hash("a"), 0xaf63dc4c8601ec8c);
+    }
+}
@@ -0,0 +1,296 @@
+use crate::alloc;
+use crate::answers::input_hash;
+use crate::days;
+use common::{input_name, read_input};
+use std::fmt;
+use std::fs::{read_to_string, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const PHASES: [&str; 3] = ["parse", "part_one", "part_two"];
+
+const RESULTS_HEADER: &str =
+    "timestamp,day,input_hash,phase,runs,min_ns,median_ns,max_ns,peak_memory_bytes";
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    // for an even number of samples, the upper of the two middle samples is used as median
+    pub fn from_samples(samples: &mut [Duration]) -> Option<Stats> {
+        samples.sort_unstable();
+        Some(Stats {
+            min: *samples.first()?,
+            median: samples[samples.len() / 2],
+            max: *samples.last()?,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct ResultLine {
+    timestamp: u64,
+    day: u8,
+    input_hash: u64,
+    phase: String,
+    runs: usize,
+    stats: Stats,
+    peak_memory: usize,
+}
+
+impl ResultLine {
+    fn parse(line: &str) -> Result<ResultLine, String> {
+        let fields: Vec<&str> = line.split(',').collect();
+        let [timestamp, day, input_hash, phase, runs, min, median, max, peak_memory] =
+            fields.as_slice()
+        else {
+            return Err(format!("expected nine fields in benchmark result '{line}'"));
+        };
+        let int = |s: &str| {
+            s.parse::<u64>()
+                .map_err(|e| format!("unable to parse '{s}' in benchmark result '{line}': {e}"))
+        };
+        Ok(ResultLine {
+            timestamp: int(timestamp)?,
+            day: u8::try_from(int(day)?)
+                .map_err(|e| format!("invalid day '{day}' in benchmark result '{line}': {e}"))?,
+            input_hash: u64::from_str_radix(input_hash, 16).map_err(|e| {
+                format!(
+                    "unable to parse input hash '{input_hash}' in benchmark result '{line}': {e}"
+                )
+            })?,
+            phase: phase.to_string(),
+            runs: int(runs)? as usize,
+            stats: Stats {
+                min: Duration::from_nanos(int(min)?),
+                median: Duration::from_nanos(int(median)?),
+                max: Duration::from_nanos(int(max)?),
+            },
+            peak_memory: int(peak_memory)? as usize,
+        })
+    }
+}
+
+impl fmt::Display for ResultLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "{},{},{:016x},{},{},{},{},{},{}",
+            self.timestamp,
+            self.day,
+            self.input_hash,
+            self.phase,
+            self.runs,
+            self.stats.min.as_nanos(),
+            self.stats.median.as_nanos(),
+            self.stats.max.as_nanos(),
+            self.peak_memory
+        )
+    }
+}
+
+fn load_results(path: &Path) -> Result<Vec<ResultLine>, String> {
+    match read_to_string(path) {
+        Ok(content) => content
+            .lines()
+            .filter(|line| !line.is_empty() && *line != RESULTS_HEADER)
+            .map(ResultLine::parse)
+            .collect(),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(format!(
+            "unable to read benchmark results '{}': {e}",
+            path.display()
+        )),
+    }
+}
+
+fn append_results(path: &Path, results: &[ResultLine]) -> Result<(), String> {
+    let is_new = !path.exists();
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("unable to open '{}': {e}", path.display()))?;
+    let mut content = String::with_capacity(results.len() * 64);
+    if is_new {
+        content.push_str(RESULTS_HEADER);
+        content.push('\n');
+    }
+    for result in results {
+        content.push_str(&result.to_string());
+        content.push('\n');
+    }
+    file.write_all(content.as_bytes())
+        .map_err(|e| format!("unable to write to '{}': {e}", path.display()))
+}
+
+fn bench_day(
+    day: u8,
+    input: &Path,
+    runs: usize,
+    timestamp: u64,
+) -> Result<Vec<ResultLine>, String> {
+    let solver = days::solver(day).ok_or_else(|| format!("there is no puzzle for day {day}"))?;
+    let content = read_input(input)?;
+    let input_hash = input_hash(&content);
+
+    let mut samples: [Vec<Duration>; 3] = [
+        Vec::with_capacity(runs),
+        Vec::with_capacity(runs),
+        Vec::with_capacity(runs),
+    ];
+    let mut peak_memory: usize = 0;
+    for _ in 0..runs {
+        let baseline = alloc::reset_peak();
+        let report = solver(&content);
+        peak_memory = peak_memory.max(alloc::peak() - baseline);
+
+        let [part_one, part_two] = report.parts?;
+        samples[0].push(report.parse_time);
+        samples[1].push(part_one.time);
+        samples[2].push(part_two.time);
+    }
+
+    Ok(PHASES
+        .iter()
+        .zip(samples.iter_mut())
+        .filter_map(|(phase, samples)| {
+            Some(ResultLine {
+                timestamp,
+                day,
+                input_hash,
+                phase: phase.to_string(),
+                runs,
+                stats: Stats::from_samples(samples)?,
+                peak_memory,
+            })
+        })
+        .collect())
+}
+
+pub fn bench(inputs: &[(u8, PathBuf)], runs: usize, results_path: &Path) -> Result<(), String> {
+    if runs == 0 {
+        return Err("the number of runs must be at least 1".to_owned());
+    }
+    let previous = load_results(results_path)?;
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let mut totals: Vec<(u8, Duration)> = Vec::with_capacity(inputs.len());
+    let mut results: Vec<ResultLine> = Vec::with_capacity(inputs.len() * PHASES.len());
+    for (day, input) in inputs {
+        let day_results = match bench_day(*day, input, runs, timestamp) {
+            Ok(day_results) => day_results,
+            Err(e) => {
+                eprintln!("Day {day}, {} failed: {e}", input_name(input));
+                continue;
+            }
+        };
+        let peak_memory = day_results.first().map(|r| r.peak_memory).unwrap_or(0);
+        println!(
+            "Day {day}, {} ({runs} runs, peak memory {})",
+            input_name(input),
+            format_bytes(peak_memory)
+        );
+        for result in &day_results {
+            let Stats { min, median, max } = result.stats;
+            print!(
+                "  {:<8}  min {min:>12.3?}  median {median:>12.3?}  max {max:>12.3?}",
+                result.phase
+            );
+            let last = previous.iter().rev().find(|p| {
+                p.day == result.day && p.input_hash == result.input_hash && p.phase == result.phase
+            });
+            if let Some(last) = last.filter(|last| !last.stats.median.is_zero()) {
+                let change = (median.as_secs_f64() / last.stats.median.as_secs_f64() - 1.0) * 100.0;
+                print!(
+                    "  (previous median {:.3?}, {change:+.1}%)",
+                    last.stats.median
+                );
+            }
+            println!();
+        }
+        totals.push((*day, day_results.iter().map(|r| r.stats.median).sum()));
+        results.extend(day_results);
+    }
+
+    if totals.len() > 1 {
+        totals.sort_unstable_by(|(_, l), (_, r)| r.cmp(l));
+        println!("Slowest days (sum of median times):");
+        for (day, total) in &totals {
+            println!("  Day {day:>2}: {total:.3?}");
+        }
+    }
+
+    append_results(results_path, &results)?;
+    println!("results have been stored in '{}'", results_path.display());
+    Ok(())
+}
+
+fn format_bytes(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{bytes} B")
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stats_from_samples_works() {
+        // given
+        let mut samples: Vec<Duration> = [5, 1, 4, 2, 3].map(Duration::from_nanos).to_vec();
+
+        // when
+        let stats = Stats::from_samples(&mut samples);
+
+        // then
+        assert_eq!(
+            stats,
+            Some(Stats {
+                min: Duration::from_nanos(1),
+                median: Duration::from_nanos(3),
+                max: Duration::from_nanos(5),
+            })
+        );
+        assert_eq!(Stats::from_samples(&mut []), None);
+    }
+
+    #[test]
+    fn result_line_display_is_inverse_of_parse() {
+        // given
+        let line = "1702425600,22,00000000000000ff,part_two,10,100,150,300,4096";
+
+        // when
+        let result = ResultLine::parse(line).expect("expected successful parsing");
+
+        // then
+        assert_eq!(result.day, 22);
+        assert_eq!(result.stats.median, Duration::from_nanos(150));
+        assert_eq!(result.to_string(), line);
+    }
+
+    #[test]
+    fn result_line_parse_rejects_invalid_days() {
+        assert!(ResultLine::parse("1702425600,300,00000000000000ff,parse,10,1,2,3,0").is_err());
+    }
+
+    #[test]
+    fn format_bytes_works() {
+        assert_eq!(format_bytes(12), "12 B");
+        assert_eq!(format_bytes(2048), "2.0 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 + 512 * 1024), "3.5 MiB");
+    }
+}
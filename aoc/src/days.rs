@@ -0,0 +1,39 @@
+use common::{solve_timed, Report};
+
+pub type Solver = fn(&str) -> Report;
+
+const SOLVERS: [Solver; 25] = [
+    solve_timed::<day_01::Puzzle>,
+    solve_timed::<day_02::Puzzle>,
+    solve_timed::<day_03::Puzzle>,
+    solve_timed::<day_04::Puzzle>,
+    solve_timed::<day_05::Puzzle>,
+    solve_timed::<day_06::Puzzle>,
+    solve_timed::<day_07::Puzzle>,
+    solve_timed::<day_08::Puzzle>,
+    solve_timed::<day_09::Puzzle>,
+    solve_timed::<day_10::Puzzle>,
+    solve_timed::<day_11::Puzzle>,
+    solve_timed::<day_12::Puzzle>,
+    solve_timed::<day_13::Puzzle>,
+    solve_timed::<day_14::Puzzle>,
+    solve_timed::<day_15::Puzzle>,
+    solve_timed::<day_16::Puzzle>,
+    solve_timed::<day_17::Puzzle>,
+    solve_timed::<day_18::Puzzle>,
+    solve_timed::<day_19::Puzzle>,
+    solve_timed::<day_20::Puzzle>,
+    solve_timed::<day_21::Puzzle>,
+    solve_timed::<day_22::Puzzle>,
+    solve_timed::<day_23::Puzzle>,
+    solve_timed::<day_24::Puzzle>,
+    solve_timed::<day_25::Puzzle>,
+];
+
+pub fn solver(day: u8) -> Option<Solver> {
+    SOLVERS.get(usize::from(day).checked_sub(1)?).copied()
+}
+
+pub fn all() -> impl Iterator<Item = u8> {
+    1..=SOLVERS.len() as u8
+}
@@ -0,0 +1,49 @@
+use crate::answers::input_hash;
+use common::{input_name, read_input, InputStore, StoredInput};
+use std::path::Path;
+
+pub fn import(
+    store: &InputStore,
+    day: u8,
+    variant: &str,
+    input: &Path,
+    force: bool,
+) -> Result<(), String> {
+    let content = read_input(input)?;
+    let path = store.import(day, variant, &content, force)?;
+    println!(
+        "imported {} as day {day}, variant '{variant}' ({:016x}) to '{}'",
+        input_name(input),
+        input_hash(&content),
+        path.display()
+    );
+    Ok(())
+}
+
+pub fn list(store: &InputStore) -> Result<(), String> {
+    let inputs = store.list()?;
+    if inputs.is_empty() {
+        println!("no inputs in '{}'", store.directory().display());
+    }
+    for StoredInput { day, variant, path } in inputs {
+        let content = read_input(&path)?;
+        println!(
+            "Day {day:>2}  {variant:<16} {:>8} bytes {:>6} lines",
+            content.len(),
+            content.lines().count()
+        );
+    }
+    Ok(())
+}
+
+// prints the hashes the answers and benchmark results of the stored inputs are recorded with
+pub fn checksum(store: &InputStore, day: Option<u8>) -> Result<(), String> {
+    for input in store.list()? {
+        if day.is_some_and(|day| day != input.day) {
+            continue;
+        }
+        let content = read_input(&input.path)?;
+        println!("{:016x}  {}", input_hash(&content), input.path.display());
+    }
+    Ok(())
+}
@@ -0,0 +1,617 @@
+use common::{
+    check_variant, input_name, option_value, read_input, Format, InputStore, DEFAULT_VARIANT, STDIN,
+};
+use std::env;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+mod alloc;
+mod answers;
+mod bench;
+mod days;
+mod inputs;
+mod verify;
+
+#[global_allocator]
+static ALLOCATOR: alloc::CountingAllocator = alloc::CountingAllocator;
+
+const USAGE: &str = "usage: aoc run <day> [<input file>|-]... [<inputs>] [--format text|json|csv]
+       aoc run all [<inputs>] [--format text|json|csv]
+       aoc verify <day> [<input file>|-]... [<inputs>] [--answers <file>] [--record]
+       aoc verify all [<inputs>] [--answers <file>] [--record]
+       aoc bench <day> [<input file>|-]... [<inputs>] [--runs <n>] [--results <file>]
+       aoc bench all [<inputs>] [--runs <n>] [--results <file>]
+       aoc inputs import <day> [<input file>|-] [--variant <name>] [--store <directory>] [--force]
+       aoc inputs list [--store <directory>]
+       aoc inputs checksum [<day>] [--store <directory>]
+
+where <inputs> is either --inputs <directory> with one '<day>.txt' per day, or any number of
+--variant <name> and --store <directory> to use inputs from the input store (default 'inputs').
+Without any inputs, the 'default' variant from the input store is used, or stdin if something is
+piped into it. '-' stands for stdin as well.";
+
+// name of the answers file in the input directory, if no other file is given
+const ANSWERS_FILE: &str = "answers.txt";
+// name of the file in the input directory where benchmark results are collected
+const BENCH_RESULTS_FILE: &str = "bench-results.csv";
+const DEFAULT_BENCH_RUNS: usize = 10;
+
+fn main() -> Result<(), String> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    match parse_args(&args, io::stdin().is_terminal())? {
+        Command::Run { selection, format } => run(&selection, format),
+        Command::Verify {
+            selection,
+            answers,
+            record,
+        } => {
+            let answers = answers.unwrap_or_else(|| selection.directory().join(ANSWERS_FILE));
+            verify::verify(&selection.inputs(), &answers, record)
+        }
+        Command::Bench {
+            selection,
+            runs,
+            results,
+        } => {
+            let results = results.unwrap_or_else(|| selection.directory().join(BENCH_RESULTS_FILE));
+            bench::bench(&selection.inputs(), runs, &results)
+        }
+        Command::Import {
+            store,
+            day,
+            variant,
+            input,
+            force,
+        } => inputs::import(&store, day, &variant, &input, force),
+        Command::List { store } => inputs::list(&store),
+        Command::Checksum { store, day } => inputs::checksum(&store, day),
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Selection {
+    Day {
+        day: u8,
+        inputs: Vec<PathBuf>,
+    },
+    All {
+        inputs: PathBuf,
+    },
+    // all days, with the given variants from the input store
+    Stored {
+        store: InputStore,
+        variants: Vec<String>,
+    },
+}
+
+impl Selection {
+    // the days to solve and their inputs, days without input are skipped
+    fn inputs(&self) -> Vec<(u8, PathBuf)> {
+        match self {
+            Selection::Day { day, inputs } => {
+                inputs.iter().map(|input| (*day, input.clone())).collect()
+            }
+            Selection::All { inputs } => days::all()
+                .filter_map(|day| {
+                    let input = inputs.join(format!("{day:02}.txt"));
+                    if input.is_file() {
+                        Some((day, input))
+                    } else {
+                        eprintln!("Day {day}: no input at '{}', skipping", input.display());
+                        None
+                    }
+                })
+                .collect(),
+            Selection::Stored { store, variants } => days::all()
+                .flat_map(|day| variants.iter().map(move |variant| (day, variant)))
+                .filter_map(|(day, variant)| {
+                    let input = store.path(day, variant).ok()?;
+                    if input.is_file() {
+                        Some((day, input))
+                    } else {
+                        eprintln!("Day {day}: no variant '{variant}' in the input store, skipping");
+                        None
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    fn directory(&self) -> PathBuf {
+        match self {
+            Selection::Day { inputs, .. } => inputs
+                .first()
+                .and_then(|input| input.parent())
+                .map(|p| p.to_path_buf())
+                .unwrap_or_default(),
+            Selection::All { inputs } => inputs.clone(),
+            Selection::Stored { store, .. } => store.directory(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Command {
+    Run {
+        selection: Selection,
+        format: Format,
+    },
+    Verify {
+        selection: Selection,
+        answers: Option<PathBuf>,
+        record: bool,
+    },
+    Bench {
+        selection: Selection,
+        runs: usize,
+        results: Option<PathBuf>,
+    },
+    Import {
+        store: InputStore,
+        day: u8,
+        variant: String,
+        input: PathBuf,
+        force: bool,
+    },
+    List {
+        store: InputStore,
+    },
+    Checksum {
+        store: InputStore,
+        day: Option<u8>,
+    },
+}
+
+// how the inputs of a selection are given, besides input files
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+struct InputOptions {
+    inputs: Option<PathBuf>,
+    variants: Vec<String>,
+    store: Option<PathBuf>,
+    stdin_is_terminal: bool,
+}
+
+impl InputOptions {
+    fn store(&self) -> InputStore {
+        self.store.as_ref().map(InputStore::new).unwrap_or_default()
+    }
+}
+
+fn parse_args(args: &[String], stdin_is_terminal: bool) -> Result<Command, String> {
+    let mut positional: Vec<&str> = Vec::with_capacity(args.len());
+    let mut input_options = InputOptions {
+        stdin_is_terminal,
+        ..InputOptions::default()
+    };
+    let mut format: Option<Format> = None;
+    let mut answers: Option<PathBuf> = None;
+    let mut record = false;
+    let mut runs: Option<usize> = None;
+    let mut results: Option<PathBuf> = None;
+    let mut force = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" => format = Some(option_value(&mut args, arg)?.parse()?),
+            "--inputs" => input_options.inputs = Some(PathBuf::from(option_value(&mut args, arg)?)),
+            "--variant" => {
+                let variant = option_value(&mut args, arg)?;
+                check_variant(variant)?;
+                input_options.variants.push(variant.to_owned());
+            }
+            "--store" => input_options.store = Some(PathBuf::from(option_value(&mut args, arg)?)),
+            "--answers" => answers = Some(PathBuf::from(option_value(&mut args, arg)?)),
+            "--record" => record = true,
+            "--runs" => {
+                let value = option_value(&mut args, arg)?;
+                runs = Some(
+                    value
+                        .parse::<usize>()
+                        .map_err(|e| format!("unable to parse number of runs '{value}': {e}"))?,
+                );
+            }
+            "--results" => results = Some(PathBuf::from(option_value(&mut args, arg)?)),
+            "--force" => force = true,
+            option if option.starts_with("--") => {
+                return Err(format!("unknown option '{option}'\n{USAGE}"));
+            }
+            arg => positional.push(arg),
+        }
+    }
+
+    let verify_options = answers.is_some() || record;
+    let bench_options = runs.is_some() || results.is_some();
+    let solve_options = format.is_some() || verify_options || bench_options;
+    match positional.as_slice() {
+        ["run", selection @ ..] if !verify_options && !bench_options && !force => {
+            Ok(Command::Run {
+                selection: parse_selection(selection, input_options)?,
+                format: format.unwrap_or(Format::Text),
+            })
+        }
+        ["verify", selection @ ..] if format.is_none() && !bench_options && !force => {
+            Ok(Command::Verify {
+                selection: parse_selection(selection, input_options)?,
+                answers,
+                record,
+            })
+        }
+        ["bench", selection @ ..] if format.is_none() && !verify_options && !force => {
+            Ok(Command::Bench {
+                selection: parse_selection(selection, input_options)?,
+                runs: runs.unwrap_or(DEFAULT_BENCH_RUNS),
+                results,
+            })
+        }
+        ["inputs", command @ ..] if !solve_options && input_options.inputs.is_none() => {
+            parse_inputs_command(command, input_options, force)
+        }
+        _ => Err(USAGE.to_owned()),
+    }
+}
+
+fn parse_inputs_command(
+    args: &[&str],
+    input_options: InputOptions,
+    force: bool,
+) -> Result<Command, String> {
+    let store = input_options.store();
+    match (args, input_options.variants.as_slice()) {
+        (["import", day, inputs @ ..], variants) if inputs.len() <= 1 && variants.len() <= 1 => {
+            Ok(Command::Import {
+                store,
+                day: parse_day(day)?,
+                variant: variants
+                    .first()
+                    .cloned()
+                    .unwrap_or_else(|| DEFAULT_VARIANT.to_owned()),
+                input: PathBuf::from(inputs.first().copied().unwrap_or(STDIN)),
+                force,
+            })
+        }
+        (["list"], []) if !force => Ok(Command::List { store }),
+        (["checksum", day @ ..], []) if day.len() <= 1 && !force => Ok(Command::Checksum {
+            store,
+            day: day.first().map(|day| parse_day(day)).transpose()?,
+        }),
+        _ => Err(USAGE.to_owned()),
+    }
+}
+
+fn parse_selection(args: &[&str], input_options: InputOptions) -> Result<Selection, String> {
+    let store = input_options.store();
+    match (args, input_options) {
+        (
+            ["all"],
+            InputOptions {
+                inputs: Some(inputs),
+                variants,
+                store: None,
+                ..
+            },
+        ) if variants.is_empty() => Ok(Selection::All { inputs }),
+        (
+            ["all"],
+            InputOptions {
+                inputs: None,
+                variants,
+                ..
+            },
+        ) => Ok(Selection::Stored {
+            store,
+            variants: if variants.is_empty() {
+                vec![DEFAULT_VARIANT.to_owned()]
+            } else {
+                variants
+            },
+        }),
+        (
+            [day, files @ ..],
+            InputOptions {
+                inputs: None,
+                variants,
+                stdin_is_terminal,
+                ..
+            },
+        ) if *day != "all" => {
+            let day = parse_day(day)?;
+            let mut inputs: Vec<PathBuf> = files.iter().map(PathBuf::from).collect();
+            for variant in &variants {
+                inputs.push(store.path(day, variant)?);
+            }
+            if inputs.is_empty() {
+                inputs.push(store.default_input(day, stdin_is_terminal));
+            }
+            Ok(Selection::Day { day, inputs })
+        }
+        _ => Err(USAGE.to_owned()),
+    }
+}
+
+fn parse_day(s: &str) -> Result<u8, String> {
+    let day = s
+        .parse::<u8>()
+        .map_err(|e| format!("unable to parse day '{s}': {e}"))?;
+    if days::solver(day).is_some() {
+        Ok(day)
+    } else {
+        Err(format!("there is no puzzle for day {day}"))
+    }
+}
+
+fn run(selection: &Selection, format: Format) -> Result<(), String> {
+    let mut failed: Vec<u8> = Vec::with_capacity(25);
+    let start = Instant::now();
+    if let Some(header) = format.header() {
+        println!("{header}");
+    }
+    for (day, input) in selection.inputs() {
+        if let Err(e) = run_day(day, &input, format) {
+            eprintln!("Day {day} failed: {e}");
+            failed.push(day);
+        }
+    }
+    // only the text output is meant for humans, keep the others machine-readable
+    if format == Format::Text && matches!(selection, Selection::All { .. }) {
+        println!("All days took {:?}", start.elapsed());
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("the following days failed: {failed:?}"))
+    }
+}
+
+fn run_day(day: u8, input: &Path, format: Format) -> Result<(), String> {
+    let solver = days::solver(day).ok_or_else(|| format!("there is no puzzle for day {day}"))?;
+    let content = read_input(input)?;
+
+    let report = solver(&content);
+    print!("{}", format.render(&report, &input_name(input)));
+
+    match report.error() {
+        Some(e) => Err(e.to_owned()),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_args_works_for_single_day() {
+        // given
+        let args = args(&["run", "17", "input.txt"]);
+
+        // when
+        let command = parse_args(&args, false);
+
+        // then
+        assert_eq!(
+            command,
+            Ok(Command::Run {
+                selection: Selection::Day {
+                    day: 17,
+                    inputs: vec![PathBuf::from("input.txt")],
+                },
+                format: Format::Text,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_args_works_for_stdin_and_multiple_inputs() {
+        // given
+        let with_stdin = args(&["run", "5"]);
+        let with_multiple = args(&["verify", "5", "alice.txt", "-", "bob.txt"]);
+
+        // when
+        let with_stdin = parse_args(&with_stdin, false);
+        let with_multiple = parse_args(&with_multiple, false);
+
+        // then
+        assert_eq!(
+            with_stdin,
+            Ok(Command::Run {
+                selection: Selection::Day {
+                    day: 5,
+                    inputs: vec![PathBuf::from("-")],
+                },
+                format: Format::Text,
+            })
+        );
+        assert_eq!(
+            with_multiple,
+            Ok(Command::Verify {
+                selection: Selection::Day {
+                    day: 5,
+                    inputs: vec![
+                        PathBuf::from("alice.txt"),
+                        PathBuf::from("-"),
+                        PathBuf::from("bob.txt")
+                    ],
+                },
+                answers: None,
+                record: false,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_args_works_for_all_days() {
+        // given
+        let args = args(&["run", "all", "--inputs", "dir/", "--format", "json"]);
+
+        // when
+        let command = parse_args(&args, false);
+
+        // then
+        assert_eq!(
+            command,
+            Ok(Command::Run {
+                selection: Selection::All {
+                    inputs: PathBuf::from("dir/"),
+                },
+                format: Format::Json,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_args_works_for_verify() {
+        // given
+        let args = args(&["verify", "all", "--inputs", "dir/", "--record"]);
+
+        // when
+        let command = parse_args(&args, false);
+
+        // then
+        assert_eq!(
+            command,
+            Ok(Command::Verify {
+                selection: Selection::All {
+                    inputs: PathBuf::from("dir/"),
+                },
+                answers: None,
+                record: true,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_args_works_for_bench() {
+        // given
+        let args = args(&["bench", "22", "input.txt", "--runs", "5"]);
+
+        // when
+        let command = parse_args(&args, false);
+
+        // then
+        assert_eq!(
+            command,
+            Ok(Command::Bench {
+                selection: Selection::Day {
+                    day: 22,
+                    inputs: vec![PathBuf::from("input.txt")],
+                },
+                runs: 5,
+                results: None,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_args_works_for_input_store() {
+        // given
+        let day = args(&["verify", "7", "--variant", "example1", "--store", "cache"]);
+        let all = args(&["bench", "all", "--variant", "alt-user"]);
+        let on_terminal = args(&["run", "7"]);
+
+        // when
+        let day = parse_args(&day, false);
+        let all = parse_args(&all, false);
+        let on_terminal = parse_args(&on_terminal, true);
+
+        // then
+        assert_eq!(
+            day,
+            Ok(Command::Verify {
+                selection: Selection::Day {
+                    day: 7,
+                    inputs: vec![PathBuf::from("cache/2023/07/example1.txt")],
+                },
+                answers: None,
+                record: false,
+            })
+        );
+        assert_eq!(
+            all,
+            Ok(Command::Bench {
+                selection: Selection::Stored {
+                    store: InputStore::default(),
+                    variants: vec!["alt-user".to_owned()],
+                },
+                runs: DEFAULT_BENCH_RUNS,
+                results: None,
+            })
+        );
+        assert_eq!(
+            on_terminal,
+            Ok(Command::Run {
+                selection: Selection::Day {
+                    day: 7,
+                    inputs: vec![PathBuf::from("inputs/2023/07/default.txt")],
+                },
+                format: Format::Text,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_args_works_for_inputs_commands() {
+        // given
+        let import = args(&[
+            "inputs",
+            "import",
+            "3",
+            "in.txt",
+            "--variant",
+            "x",
+            "--force",
+        ]);
+        let checksum = args(&["inputs", "checksum", "--store", "cache"]);
+
+        // when
+        let import = parse_args(&import, false);
+        let checksum = parse_args(&checksum, false);
+
+        // then
+        assert_eq!(
+            import,
+            Ok(Command::Import {
+                store: InputStore::default(),
+                day: 3,
+                variant: "x".to_owned(),
+                input: PathBuf::from("in.txt"),
+                force: true,
+            })
+        );
+        assert_eq!(
+            checksum,
+            Ok(Command::Checksum {
+                store: InputStore::new("cache"),
+                day: None,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_args_rejects_invalid_arguments() {
+        assert!(parse_args(&args(&["run", "0", "input.txt"]), false).is_err());
+        assert!(parse_args(&args(&["run", "26", "input.txt"]), false).is_err());
+        assert!(parse_args(&args(&["run", "all", "dir/"]), false).is_err());
+        assert!(parse_args(&args(&["test", "1", "input.txt"]), false).is_err());
+        assert!(parse_args(&args(&["run", "1", "input.txt", "--format", "xml"]), false).is_err());
+        assert!(parse_args(&args(&["run", "1", "input.txt", "--record"]), false).is_err());
+        assert!(parse_args(&args(&["verify", "1", "in.txt", "--format", "csv"]), false).is_err());
+        assert!(parse_args(&args(&["bench", "1", "in.txt", "--runs", "many"]), false).is_err());
+        assert!(parse_args(&args(&["bench", "1", "in.txt", "--record"]), false).is_err());
+        assert!(parse_args(&args(&["run", "1", "--variant", "a/b"]), false).is_err());
+        assert!(parse_args(&args(&["run", "1", "--force"]), false).is_err());
+        assert!(parse_args(
+            &args(&["run", "all", "--inputs", "d", "--variant", "x"]),
+            false
+        )
+        .is_err());
+        assert!(parse_args(&args(&["inputs", "list", "--format", "csv"]), false).is_err());
+        assert!(parse_args(&args(&["inputs", "import", "1", "a.txt", "b.txt"]), false).is_err());
+    }
+}
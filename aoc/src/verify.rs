@@ -0,0 +1,212 @@
+use crate::answers::{input_hash, Answers};
+use crate::days;
+use common::{input_name, read_input, Answer};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Status {
+    Pass {
+        answer: String,
+    },
+    // the solver gives a different answer than the one that has been recorded
+    Regression {
+        expected: String,
+        actual: String,
+    },
+    // the solver gives no answer at all
+    Error {
+        expected: Option<String>,
+        reason: String,
+    },
+    // there is no recorded answer for this input yet
+    New {
+        answer: String,
+    },
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Status::Pass { answer } => write!(f, "pass ({answer})"),
+            Status::Regression { expected, actual } => {
+                write!(f, "REGRESSION: expected {expected}, got {actual}")
+            }
+            Status::Error {
+                expected: Some(expected),
+                reason,
+            } => write!(f, "ERROR: expected {expected}, got no answer: {reason}"),
+            Status::Error {
+                expected: None,
+                reason,
+            } => write!(f, "ERROR: no answer: {reason}"),
+            Status::New { answer } => write!(f, "no expected answer yet ({answer})"),
+        }
+    }
+}
+
+pub fn check(expected: Option<&str>, answer: Result<&Answer, &str>) -> Status {
+    let expected = expected.map(|e| e.to_owned());
+    let answer = match answer {
+        Ok(Answer::Unsolved(reason)) => {
+            return Status::Error {
+                expected,
+                reason: reason.clone(),
+            }
+        }
+        Err(reason) => {
+            return Status::Error {
+                expected,
+                reason: reason.to_owned(),
+            }
+        }
+        Ok(answer) => answer.to_string(),
+    };
+    match expected {
+        None => Status::New { answer },
+        Some(expected) if expected == answer => Status::Pass { answer },
+        Some(expected) => Status::Regression {
+            expected,
+            actual: answer,
+        },
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+struct Summary {
+    passed: usize,
+    regressions: usize,
+    errors: usize,
+    new: usize,
+}
+
+pub fn verify(inputs: &[(u8, PathBuf)], answers_path: &Path, record: bool) -> Result<(), String> {
+    let mut answers = Answers::load(answers_path)?;
+    let mut summary = Summary::default();
+
+    for (day, input) in inputs {
+        let solver =
+            days::solver(*day).ok_or_else(|| format!("there is no puzzle for day {day}"))?;
+        // an unreadable input fails like an input without answers, the others are still checked
+        let content = match read_input(input) {
+            Ok(content) => content,
+            Err(reason) => {
+                let status = Status::Error {
+                    expected: None,
+                    reason,
+                };
+                println!("Day {day}, {}: {status}", input_name(input));
+                summary.errors += 1;
+                continue;
+            }
+        };
+        let hash = input_hash(&content);
+        let report = solver(&content);
+
+        for part in 1..=2u8 {
+            let answer = match &report.parts {
+                Ok(parts) => Ok(&parts[part as usize - 1].answer),
+                Err(e) => Err(e.as_str()),
+            };
+            let status = check(answers.get(*day, part, hash), answer);
+            println!("Day {day} part {part}, {}: {status}", input_name(input));
+            match status {
+                Status::Pass { .. } => summary.passed += 1,
+                Status::Regression { .. } => summary.regressions += 1,
+                Status::Error { .. } => summary.errors += 1,
+                Status::New { answer } => {
+                    summary.new += 1;
+                    if record {
+                        answers.insert(*day, part, hash, answer);
+                    }
+                }
+            }
+        }
+    }
+
+    println!(
+        "{} passed, {} regressions, {} errors, {} without expected answer",
+        summary.passed, summary.regressions, summary.errors, summary.new
+    );
+    if record && summary.new > 0 {
+        answers.save(answers_path)?;
+        println!(
+            "recorded {} new answers in '{}'",
+            summary.new,
+            answers_path.display()
+        );
+    }
+
+    if summary.regressions > 0 || summary.errors > 0 {
+        Err("verification failed".to_owned())
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env::temp_dir;
+    use std::fs::{create_dir_all, remove_dir_all, write};
+
+    #[test]
+    fn check_works() {
+        assert_eq!(
+            check(Some("42"), Ok(&Answer::Integer(42))),
+            Status::Pass {
+                answer: "42".to_owned()
+            }
+        );
+        assert_eq!(
+            check(Some("42"), Ok(&Answer::Integer(41))),
+            Status::Regression {
+                expected: "42".to_owned(),
+                actual: "41".to_owned()
+            }
+        );
+        assert_eq!(
+            check(None, Ok(&Answer::Text("ABC".to_owned()))),
+            Status::New {
+                answer: "ABC".to_owned()
+            }
+        );
+        assert_eq!(
+            check(Some("42"), Ok(&Answer::unsolved("no path"))),
+            Status::Error {
+                expected: Some("42".to_owned()),
+                reason: "no path".to_owned()
+            }
+        );
+        assert_eq!(
+            check(None, Err("invalid input")),
+            Status::Error {
+                expected: None,
+                reason: "invalid input".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn verify_records_answers_despite_unreadable_inputs() {
+        // given
+        let dir = temp_dir().join(format!("aoc-verify-test-{}", std::process::id()));
+        let _ = remove_dir_all(&dir);
+        create_dir_all(&dir).expect("expected a temporary directory");
+        let input = dir.join("01.txt");
+        write(&input, "1abc2\n").expect("expected a writable input");
+        let answers_path = dir.join("answers.txt");
+        let inputs = vec![(1, dir.join("missing.txt")), (1, input)];
+
+        // when
+        let result = verify(&inputs, &answers_path, true);
+        let answers = Answers::load(&answers_path);
+        let _ = remove_dir_all(&dir);
+
+        // then
+        assert!(result.is_err());
+        let answers = answers.expect("expected recorded answers");
+        assert_eq!(answers.get(1, 1, input_hash("1abc2\n")), Some("12"));
+        assert_eq!(answers.get(1, 2, input_hash("1abc2\n")), Some("12"));
+    }
+}
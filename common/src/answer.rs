@@ -0,0 +1,93 @@
+use std::fmt;
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    // the puzzle could not be solved for this input, the string explains why
+    Unsolved(String),
+}
+
+impl Answer {
+    pub fn unsolved(reason: impl Into<String>) -> Self {
+        Answer::Unsolved(reason.into())
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Answer::Integer(n) => write!(f, "{n}"),
+            Answer::Text(s) => write!(f, "{s}"),
+            Answer::Unsolved(reason) => write!(f, "no answer ({reason})"),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Integer(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        i128::try_from(n)
+            .map(Answer::Integer)
+            .unwrap_or_else(|_| Answer::Text(n.to_string()))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_owned())
+    }
+}
+
+impl<T, E> From<Result<T, E>> for Answer
+where
+    T: Into<Answer>,
+    E: fmt::Display,
+{
+    fn from(result: Result<T, E>) -> Self {
+        match result {
+            Ok(value) => value.into(),
+            Err(e) => Answer::Unsolved(e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn answer_from_integers_works() {
+        assert_eq!(Answer::from(42u32), Answer::Integer(42));
+        assert_eq!(Answer::from(-3i64), Answer::Integer(-3));
+        assert_eq!(Answer::from(u128::MAX), Answer::Text(u128::MAX.to_string()));
+    }
+
+    #[test]
+    fn answer_from_result_works() {
+        assert_eq!(Answer::from(Ok::<usize, String>(6)), Answer::Integer(6));
+        assert_eq!(
+            Answer::from(Err::<usize, String>("no starting nodes found".to_owned())),
+            Answer::Unsolved("no starting nodes found".to_owned())
+        );
+    }
+}
@@ -0,0 +1,498 @@
+use std::env;
+use std::fs::read_to_string;
+use std::io::{self, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+
+mod answer;
+mod output;
+mod parse;
+mod rational;
+mod report;
+mod store;
+
+pub use answer::Answer;
+pub use output::Format;
+pub use parse::{parse_lines, parse_number, split_once, ErrorKind, ParseError};
+pub use rational::Rational;
+pub use report::{solve_timed, Report, TimedAnswer};
+pub use store::{check_variant, InputStore, StoredInput, DEFAULT_STORE, DEFAULT_VARIANT, YEAR};
+
+pub trait Solution {
+    const DAY: u8;
+
+    // the parsed puzzle input, may borrow from the raw input
+    type Input<'a>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+
+    fn part_one(input: &Self::Input<'_>) -> Answer;
+
+    fn part_two(input: &Self::Input<'_>) -> Answer;
+}
+
+pub fn solve<S: Solution>(input: &str) -> Result<(Answer, Answer), ParseError> {
+    let parsed = S::parse(input)?;
+    Ok((S::part_one(&parsed), S::part_two(&parsed)))
+}
+
+// input file name that stands for the standard input
+pub const STDIN: &str = "-";
+
+pub fn read_input(path: &Path) -> Result<String, String> {
+    if path == Path::new(STDIN) {
+        let mut content = String::with_capacity(32 * 1024);
+        io::stdin()
+            .read_to_string(&mut content)
+            .map_err(|e| format!("unable to read input from stdin: {e}"))?;
+        Ok(content)
+    } else {
+        read_to_string(path)
+            .map_err(|e| format!("unable to read input file '{}': {e}", path.display()))
+    }
+}
+
+// how an input is named in the reports
+pub fn input_name(path: &Path) -> String {
+    if path == Path::new(STDIN) {
+        "<stdin>".to_owned()
+    } else {
+        path.display().to_string()
+    }
+}
+
+/// An extra mode of a day's binary, which is run on the inputs instead of solving the puzzle when
+/// one of its flags is given, see [`main_with`].
+pub trait Mode {
+    /// The options that select the mode, e.g. `--render`.
+    fn flags(&self) -> &[&str];
+
+    /// The options of the mode for the usage message, e.g. `--render ansi|html`.
+    fn usage(&self) -> &str;
+
+    /// Handles an argument that is not one of the common options, including the flags of the
+    /// mode. The value of an option is taken from the following arguments, see [`option_value`].
+    /// Returns `false` for arguments the mode does not know, which are inputs unless they start
+    /// with `--`.
+    fn argument(
+        &mut self,
+        arg: &str,
+        args: &mut dyn Iterator<Item = &String>,
+    ) -> Result<bool, String>;
+
+    /// Whether the mode runs on puzzle inputs. A mode without inputs does all of its work in
+    /// [`Mode::start`], and does not accept `--variant` or `--store`.
+    fn reads_inputs(&self) -> bool {
+        true
+    }
+
+    /// Called once all arguments are handled, before the first input.
+    fn start(&mut self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Runs the mode on one input and prints the results. A failed input does not stop the mode
+    /// from running on the others.
+    fn run(&mut self, input: &Path, name: &str) -> Result<(), String>;
+}
+
+const INPUT_USAGE: &str = "[<input file>|-]... [--variant <name>]... [--store <directory>]";
+
+fn usage(modes: &[&mut dyn Mode], mode: Option<usize>) -> String {
+    let with_inputs = |mode: &dyn Mode| {
+        if mode.reads_inputs() {
+            format!("{} {INPUT_USAGE}", mode.usage())
+        } else {
+            mode.usage().to_owned()
+        }
+    };
+    match mode {
+        Some(i) => format!("usage: {}", with_inputs(&*modes[i])),
+        None => {
+            let mut usage = format!("usage: {INPUT_USAGE} [--format text|json|csv]");
+            for mode in modes {
+                usage.push_str("\n   or: ");
+                usage.push_str(&with_inputs(&**mode));
+            }
+            usage
+        }
+    }
+}
+
+/// The value of an option, taken from the following arguments.
+pub fn option_value<'a>(
+    args: &mut dyn Iterator<Item = &'a String>,
+    option: &str,
+) -> Result<&'a str, String> {
+    args.next()
+        .map(|s| s.as_str())
+        .ok_or_else(|| format!("missing value for option '{option}'"))
+}
+
+// the mode selected by one of its flags, if any
+fn select_mode(args: &[String], modes: &[&mut dyn Mode]) -> Result<Option<usize>, String> {
+    let mut selected: Option<(usize, &str)> = None;
+    for arg in args {
+        let Some(i) = modes.iter().position(|m| m.flags().contains(&arg.as_str())) else {
+            continue;
+        };
+        match selected {
+            Some((mode, flag)) if mode != i => {
+                return Err(format!(
+                    "option '{flag}' can not be combined with '{arg}'\n{}",
+                    usage(modes, None)
+                ));
+            }
+            _ => selected = Some((i, arg)),
+        }
+    }
+    Ok(selected.map(|(i, _)| i))
+}
+
+// the common options given in the arguments, the other ones are handled by the mode
+struct Options<'a> {
+    inputs: Vec<PathBuf>,
+    variants: Vec<&'a str>,
+    store: InputStore,
+    format: Format,
+}
+
+fn parse_options<'a>(
+    args: &'a [String],
+    mut mode: Option<&mut dyn Mode>,
+) -> Result<Options<'a>, String> {
+    let mut options = Options {
+        inputs: Vec::with_capacity(args.len()),
+        variants: Vec::with_capacity(args.len()),
+        store: InputStore::default(),
+        format: Format::Text,
+    };
+    let reads_inputs = mode.as_ref().is_none_or(|mode| mode.reads_inputs());
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" if mode.is_none() => {
+                options.format = option_value(&mut args, arg)?.parse()?
+            }
+            "--variant" if reads_inputs => options.variants.push(option_value(&mut args, arg)?),
+            "--store" if reads_inputs => {
+                options.store = InputStore::new(option_value(&mut args, arg)?)
+            }
+            other => {
+                let handled = match mode.as_deref_mut() {
+                    Some(mode) => mode.argument(other, &mut args)?,
+                    None => false,
+                };
+                if handled {
+                    continue;
+                } else if other.starts_with("--") {
+                    return Err(format!("unknown option '{other}'"));
+                } else if reads_inputs {
+                    options.inputs.push(PathBuf::from(other));
+                } else {
+                    return Err(format!("unexpected argument '{other}'"));
+                }
+            }
+        }
+    }
+    Ok(options)
+}
+
+// Variants are looked up in the input store. Without input files or variants, the stored default
+// input is used, or stdin if something is piped into it. A flag of one of the modes selects that
+// mode, which handles the options that are not common to all modes.
+fn parse_args(
+    args: &[String],
+    day: u8,
+    stdin_is_terminal: bool,
+    modes: &mut [&mut dyn Mode],
+) -> Result<(Vec<PathBuf>, Format, Option<usize>), String> {
+    let mode = select_mode(args, modes)?;
+    let selected = mode.map(|i| &mut *modes[i] as &mut dyn Mode);
+    let options =
+        parse_options(args, selected).map_err(|e| format!("{e}\n{}", usage(modes, mode)))?;
+    let mut inputs = options.inputs;
+    for variant in options.variants {
+        inputs.push(options.store.path(day, variant)?);
+    }
+    if inputs.is_empty() && mode.is_none_or(|i| modes[i].reads_inputs()) {
+        inputs.push(options.store.default_input(day, stdin_is_terminal));
+    }
+    Ok((inputs, options.format, mode))
+}
+
+// shared main function for the binaries of the single days, every input is solved on its own
+pub fn main<S: Solution>() -> Result<(), String> {
+    main_with::<S>(&mut [])
+}
+
+/// Like [`main`], but with extra modes of the day's binary. When a flag of one of the modes is
+/// given, that mode is run on every input instead of solving the puzzle.
+pub fn main_with<S: Solution>(modes: &mut [&mut dyn Mode]) -> Result<(), String> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (inputs, format, mode) = parse_args(&args, S::DAY, io::stdin().is_terminal(), modes)?;
+
+    let mut failed: Vec<String> = Vec::with_capacity(inputs.len());
+    match mode {
+        Some(i) => {
+            let mode = &mut *modes[i];
+            mode.start()?;
+            for input in &inputs {
+                let name = input_name(input);
+                if let Err(e) = mode.run(input, &name) {
+                    eprintln!("{name}: {e}");
+                    failed.push(name);
+                }
+            }
+        }
+        None => {
+            if let Some(header) = format.header() {
+                println!("{header}");
+            }
+            for input in &inputs {
+                let result = read_input(input).and_then(|content| {
+                    let report = solve_timed::<S>(&content);
+                    print!("{}", format.render(&report, &input_name(input)));
+                    match report.error() {
+                        Some(e) => Err(e.to_owned()),
+                        None => Ok(()),
+                    }
+                });
+                if let Err(e) = result {
+                    eprintln!("{}: {e}", input_name(input));
+                    failed.push(input_name(input));
+                }
+            }
+        }
+    }
+
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("the following inputs failed: {failed:?}"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_args_reads_stdin_without_input_files() {
+        // given
+        let args = args(&["--format", "csv"]);
+
+        // when
+        let parsed = parse_args(&args, 5, false, &mut []);
+
+        // then
+        assert_eq!(parsed, Ok((vec![PathBuf::from(STDIN)], Format::Csv, None)));
+    }
+
+    #[test]
+    fn parse_args_works_for_multiple_input_files() {
+        // given
+        let args = args(&["alice.txt", "-", "bob.txt"]);
+
+        // when
+        let parsed = parse_args(&args, 5, false, &mut []);
+
+        // then
+        assert_eq!(
+            parsed,
+            Ok((
+                vec![
+                    PathBuf::from("alice.txt"),
+                    PathBuf::from(STDIN),
+                    PathBuf::from("bob.txt")
+                ],
+                Format::Text,
+                None
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_args_resolves_stored_inputs() {
+        // given
+        let with_variants = args(&["--variant", "example1", "--store", "in", "--variant", "alt"]);
+
+        // when
+        let with_variants = parse_args(&with_variants, 5, false, &mut []);
+        let on_terminal = parse_args(&[], 5, true, &mut []);
+
+        // then
+        assert_eq!(
+            with_variants,
+            Ok((
+                vec![
+                    PathBuf::from("in/2023/05/example1.txt"),
+                    PathBuf::from("in/2023/05/alt.txt")
+                ],
+                Format::Text,
+                None
+            ))
+        );
+        assert_eq!(
+            on_terminal,
+            Ok((
+                vec![PathBuf::from("inputs/2023/05/default.txt")],
+                Format::Text,
+                None
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_args_rejects_invalid_options() {
+        assert!(parse_args(&args(&["input.txt", "--format"]), 1, false, &mut []).is_err());
+        assert!(parse_args(&args(&["input.txt", "--format", "xml"]), 1, false, &mut []).is_err());
+        assert!(parse_args(&args(&["input.txt", "--verbose"]), 1, false, &mut []).is_err());
+        assert!(parse_args(&args(&["--variant", "../secret"]), 1, false, &mut []).is_err());
+    }
+
+    #[derive(Default)]
+    struct Render {
+        html: bool,
+    }
+
+    impl Mode for Render {
+        fn flags(&self) -> &[&str] {
+            &["--render"]
+        }
+
+        fn usage(&self) -> &str {
+            "--render ansi|html"
+        }
+
+        fn argument(
+            &mut self,
+            arg: &str,
+            args: &mut dyn Iterator<Item = &String>,
+        ) -> Result<bool, String> {
+            match arg {
+                "--render" => match option_value(args, arg)? {
+                    "ansi" => self.html = false,
+                    "html" => self.html = true,
+                    other => return Err(format!("unknown format '{other}'")),
+                },
+                _ => return Ok(false),
+            }
+            Ok(true)
+        }
+
+        fn run(&mut self, _: &Path, _: &str) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct Complete {
+        hands: Vec<String>,
+    }
+
+    impl Mode for Complete {
+        fn flags(&self) -> &[&str] {
+            &["--complete"]
+        }
+
+        fn usage(&self) -> &str {
+            "--complete <hand>..."
+        }
+
+        fn argument(
+            &mut self,
+            arg: &str,
+            _: &mut dyn Iterator<Item = &String>,
+        ) -> Result<bool, String> {
+            match arg {
+                "--complete" => {}
+                hand if !hand.starts_with("--") => self.hands.push(hand.to_owned()),
+                _ => return Ok(false),
+            }
+            Ok(true)
+        }
+
+        fn reads_inputs(&self) -> bool {
+            false
+        }
+
+        fn run(&mut self, _: &Path, _: &str) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn parse_args_selects_mode_with_its_options() {
+        // given
+        let args = args(&["a.txt", "--render", "html", "--variant", "alt"]);
+        let mut render = Render::default();
+        let mut complete = Complete::default();
+
+        // when
+        let parsed = parse_args(&args, 5, false, &mut [&mut complete, &mut render]);
+
+        // then
+        assert_eq!(
+            parsed,
+            Ok((
+                vec![
+                    PathBuf::from("a.txt"),
+                    PathBuf::from("inputs/2023/05/alt.txt")
+                ],
+                Format::Text,
+                Some(1)
+            ))
+        );
+        assert!(render.html);
+        assert!(complete.hands.is_empty());
+    }
+
+    #[test]
+    fn parse_args_reads_stdin_in_modes_without_input_files() {
+        // given
+        let args = args(&["--render", "ansi"]);
+
+        // when
+        let parsed = parse_args(&args, 5, false, &mut [&mut Render::default()]);
+
+        // then
+        assert_eq!(
+            parsed,
+            Ok((vec![PathBuf::from(STDIN)], Format::Text, Some(0)))
+        );
+    }
+
+    #[test]
+    fn parse_args_passes_arguments_to_modes_without_inputs() {
+        // given
+        let args = args(&["--complete", "AA??K", "QQ???"]);
+        let mut complete = Complete::default();
+
+        // when
+        let parsed = parse_args(&args, 7, true, &mut [&mut Render::default(), &mut complete]);
+
+        // then
+        assert_eq!(parsed, Ok((vec![], Format::Text, Some(1))));
+        assert_eq!(complete.hands, vec!["AA??K", "QQ???"]);
+    }
+
+    #[test]
+    fn parse_args_rejects_invalid_mode_options() {
+        let parse = |arguments: &[&str]| {
+            let modes: &mut [&mut dyn Mode] =
+                &mut [&mut Render::default(), &mut Complete::default()];
+            parse_args(&args(arguments), 7, false, modes)
+        };
+        assert!(parse(&["--render"]).is_err());
+        assert!(parse(&["--render", "svg"]).is_err());
+        assert!(parse(&["--render", "ansi", "--format", "csv"]).is_err());
+        assert!(parse(&["--render", "ansi", "--verbose"]).is_err());
+        assert!(parse(&["--render", "ansi", "--complete", "AA???"]).is_err());
+        assert!(parse(&["--complete", "AA???", "--variant", "alt"]).is_err());
+        assert!(parse(&["--complete", "AA???", "--store", "in"]).is_err());
+    }
+}
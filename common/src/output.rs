@@ -0,0 +1,266 @@
+use crate::{Answer, Report};
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Format {
+    Text,
+    // one JSON object per line and part
+    Json,
+    // one CSV row per part, with a header line
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!(
+                "unknown output format '{s}', expected one of 'text', 'json', 'csv'"
+            )),
+        }
+    }
+}
+
+const CSV_HEADER: &str = "day,input,part,answer,parse_time_ns,solve_time_ns,error";
+
+impl Format {
+    // some formats need a header before the first report
+    pub fn header(self) -> Option<&'static str> {
+        match self {
+            Format::Csv => Some(CSV_HEADER),
+            Format::Text | Format::Json => None,
+        }
+    }
+
+    // renders the report for the given input file name, including a trailing newline
+    pub fn render(self, report: &Report, input: &str) -> String {
+        match self {
+            Format::Text => render_text(report, input),
+            Format::Json => rows(report, input).map(|row| row.json() + "\n").collect(),
+            Format::Csv => rows(report, input).map(|row| row.csv() + "\n").collect(),
+        }
+    }
+}
+
+fn render_text(report: &Report, input: &str) -> String {
+    let mut out = String::with_capacity(128);
+    match &report.parts {
+        Ok([part_one, part_two]) => {
+            // writing to a string can not fail
+            let _ = writeln!(
+                out,
+                "Day {}, {input} (parsing took {:?})",
+                report.day, report.parse_time
+            );
+            let _ = writeln!(
+                out,
+                "Part one: {} (took {:?})",
+                part_one.answer, part_one.time
+            );
+            let _ = writeln!(
+                out,
+                "Part two: {} (took {:?})",
+                part_two.answer, part_two.time
+            );
+        }
+        Err(e) => {
+            let _ = writeln!(
+                out,
+                "Day {}, {input}: unable to parse input: {e}",
+                report.day
+            );
+        }
+    }
+    out
+}
+
+struct Row<'a> {
+    day: u8,
+    input: &'a str,
+    part: u8,
+    answer: Option<&'a Answer>,
+    parse_time: Duration,
+    solve_time: Option<Duration>,
+    error: Option<&'a str>,
+}
+
+fn rows<'a>(report: &'a Report, input: &'a str) -> impl Iterator<Item = Row<'a>> {
+    (0..2u8).map(move |i| {
+        let (answer, solve_time, error) = match &report.parts {
+            Ok(parts) => {
+                let part = &parts[i as usize];
+                match &part.answer {
+                    Answer::Unsolved(reason) => (None, Some(part.time), Some(reason.as_str())),
+                    answer => (Some(answer), Some(part.time), None),
+                }
+            }
+            Err(e) => (None, None, Some(e.as_str())),
+        };
+        Row {
+            day: report.day,
+            input,
+            part: i + 1,
+            answer,
+            parse_time: report.parse_time,
+            solve_time,
+            error,
+        }
+    })
+}
+
+impl Row<'_> {
+    fn json(&self) -> String {
+        let answer = match self.answer {
+            Some(Answer::Integer(n)) => n.to_string(),
+            Some(Answer::Text(s)) => json_string(s),
+            Some(Answer::Unsolved(_)) | None => "null".to_owned(),
+        };
+        let solve_time = self
+            .solve_time
+            .map(|t| t.as_nanos().to_string())
+            .unwrap_or_else(|| "null".to_owned());
+        let error = self
+            .error
+            .map(json_string)
+            .unwrap_or_else(|| "null".to_owned());
+        format!(
+            r#"{{"day":{},"input":{},"part":{},"answer":{answer},"parse_time_ns":{},"solve_time_ns":{solve_time},"error":{error}}}"#,
+            self.day,
+            json_string(self.input),
+            self.part,
+            self.parse_time.as_nanos(),
+        )
+    }
+
+    fn csv(&self) -> String {
+        let answer = match self.answer {
+            Some(Answer::Integer(n)) => n.to_string(),
+            Some(Answer::Text(s)) => csv_field(s),
+            Some(Answer::Unsolved(_)) | None => String::new(),
+        };
+        let solve_time = self
+            .solve_time
+            .map(|t| t.as_nanos().to_string())
+            .unwrap_or_default();
+        let error = self.error.map(csv_field).unwrap_or_default();
+        format!(
+            "{},{},{},{answer},{},{solve_time},{error}",
+            self.day,
+            csv_field(self.input),
+            self.part,
+            self.parse_time.as_nanos()
+        )
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::TimedAnswer;
+
+    fn example_report() -> Report {
+        Report {
+            day: 20,
+            parse_time: Duration::from_nanos(1500),
+            parts: Ok([
+                TimedAnswer {
+                    answer: Answer::Integer(32000000),
+                    time: Duration::from_nanos(200),
+                },
+                TimedAnswer {
+                    answer: Answer::unsolved("no \"rx\" module, no shortcut"),
+                    time: Duration::from_nanos(30),
+                },
+            ]),
+        }
+    }
+
+    #[test]
+    fn render_json_works_for_example() {
+        // given
+        let report = example_report();
+
+        // when
+        let json = Format::Json.render(&report, "20.txt");
+
+        // then
+        assert_eq!(
+            json,
+            r#"{"day":20,"input":"20.txt","part":1,"answer":32000000,"parse_time_ns":1500,"solve_time_ns":200,"error":null}
+{"day":20,"input":"20.txt","part":2,"answer":null,"parse_time_ns":1500,"solve_time_ns":30,"error":"no \"rx\" module, no shortcut"}
+"#
+        );
+    }
+
+    #[test]
+    fn render_csv_works_for_example() {
+        // given
+        let report = example_report();
+
+        // when
+        let csv = Format::Csv.render(&report, "20.txt");
+
+        // then
+        assert_eq!(
+            csv,
+            r#"20,20.txt,1,32000000,1500,200,
+20,20.txt,2,,1500,30,"no ""rx"" module, no shortcut"
+"#
+        );
+    }
+
+    #[test]
+    fn render_json_works_for_parse_errors() {
+        // given
+        let report = Report {
+            day: 2,
+            parse_time: Duration::from_nanos(10),
+            parts: Err("unknown color: 'pink'".to_owned()),
+        };
+
+        // when
+        let json = Format::Json.render(&report, "-");
+
+        // then
+        assert_eq!(
+            json,
+            r#"{"day":2,"input":"-","part":1,"answer":null,"parse_time_ns":10,"solve_time_ns":null,"error":"unknown color: 'pink'"}
+{"day":2,"input":"-","part":2,"answer":null,"parse_time_ns":10,"solve_time_ns":null,"error":"unknown color: 'pink'"}
+"#
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ErrorKind {
+    // contains the reason why the number could not be parsed
+    InvalidNumber(String),
+    // a token that is not allowed here, e.g. an unknown tile or a name that is never defined
+    Unknown(&'static str),
+    // something that is required here, e.g. a separator or a section of the input
+    Missing(&'static str),
+    Invalid(String),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            ErrorKind::InvalidNumber(reason) => write!(f, "invalid number ({reason})"),
+            ErrorKind::Unknown(what) => write!(f, "unknown {what}"),
+            ErrorKind::Missing(what) => write!(f, "missing {what}"),
+            ErrorKind::Invalid(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    // line and column are 1-based, the column counts characters, not bytes
+    pub line: usize,
+    pub column: usize,
+    // the part of the input that caused the error
+    pub text: String,
+    pub kind: ErrorKind,
+}
+
+impl ParseError {
+    // `text` is expected to be a slice of `input`, line and column are derived from its position
+    pub fn at(input: &str, text: &str, kind: ErrorKind) -> ParseError {
+        ParseError {
+            line: 1,
+            column: 1,
+            text: text.to_owned(),
+            kind,
+        }
+        .within(input, text)
+    }
+
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    // for an error in `inner`, which is a slice of `outer`: makes the position relative to `outer`
+    pub fn within(mut self, outer: &str, inner: &str) -> Self {
+        let offset = (inner.as_ptr() as usize).wrapping_sub(outer.as_ptr() as usize);
+        let Some(before) = outer.get(..offset) else {
+            return self;
+        };
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        if self.line == 1 {
+            self.column += before[line_start..].chars().count();
+        }
+        self.line += before.matches('\n').count();
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.kind
+        )?;
+        if !self.text.is_empty() {
+            write!(f, " in '{}'", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+// parses every line of the input on its own, errors get the number of the line
+pub fn parse_lines<'a, T>(
+    input: &'a str,
+    mut parse_line: impl FnMut(&'a str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_line(line).map_err(|e| e.on_line(i + 1)))
+        .collect()
+}
+
+// parses `text`, which is expected to be a slice of `input`
+pub fn parse_number<T>(input: &str, text: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    text.parse::<T>()
+        .map_err(|e| ParseError::at(input, text, ErrorKind::InvalidNumber(e.to_string())))
+}
+
+// splits `text` (a slice of `input`) at the first occurrence of `separator`
+pub fn split_once<'a>(
+    input: &str,
+    text: &'a str,
+    separator: &str,
+    what: &'static str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    text.split_once(separator)
+        .ok_or_else(|| ParseError::at(input, text, ErrorKind::Missing(what)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = r#"first line
+second: 12x
+"#;
+
+    #[test]
+    fn at_finds_line_and_column() {
+        // given
+        let text = &EXAMPLE[19..22];
+
+        // when
+        let error = ParseError::at(EXAMPLE, text, ErrorKind::Unknown("tile"));
+
+        // then
+        assert_eq!(error.line, 2);
+        assert_eq!(error.column, 9);
+        assert_eq!(error.to_string(), "line 2, column 9: unknown tile in '12x'");
+    }
+
+    #[test]
+    fn parse_lines_reports_line_of_error() {
+        // when
+        let result = parse_lines(EXAMPLE, |line| {
+            let (_, number) = split_once(line, line, ": ", "colon")?;
+            parse_number::<u32>(line, number)
+        });
+
+        // then
+        let error = result.expect_err("expected parsing to fail");
+        assert_eq!(error.line, 1);
+        assert_eq!(error.column, 1);
+        assert_eq!(error.kind, ErrorKind::Missing("colon"));
+    }
+
+    #[test]
+    fn parse_number_reports_column_of_error() {
+        // given
+        let line = EXAMPLE.lines().nth(1).expect("expected two lines");
+
+        // when
+        let error = parse_number::<u32>(line, &line[8..])
+            .expect_err("expected parsing to fail")
+            .on_line(2);
+
+        // then
+        assert_eq!((error.line, error.column), (2, 9));
+        assert_eq!(error.text, "12x");
+        assert!(matches!(error.kind, ErrorKind::InvalidNumber(_)));
+    }
+
+    #[test]
+    fn within_shifts_position() {
+        // given
+        let block = &EXAMPLE[11..];
+        let error = ParseError::at(block, &block[8..10], ErrorKind::Missing("x"));
+
+        // when
+        let error = error.within(EXAMPLE, block);
+
+        // then
+        assert_eq!((error.line, error.column), (2, 9));
+    }
+}
@@ -0,0 +1,271 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops;
+
+/// An exact rational number, as numerator and denominator.
+///
+/// Equality and ordering compare the values, so `1/2 == 2/4`. Multiplication and division reduce
+/// their result, addition and subtraction do not. The operators panic or overflow like the ones
+/// of integers, the `checked_` methods return `None` instead and always reduce their result.
+#[derive(Copy, Clone)]
+pub struct Rational(i128, i128);
+
+impl Rational {
+    pub const ZERO: Rational = Rational::integer(0);
+    pub const ONE: Rational = Rational::integer(1);
+
+    pub const fn integer(n: i128) -> Self {
+        Rational(n, 1)
+    }
+
+    /// Panics if the denominator is 0.
+    pub fn new(counter: i128, denominator: i128) -> Rational {
+        if denominator == 0 {
+            panic!("rational denominator must not be 0");
+        }
+        Rational(counter, denominator)
+    }
+
+    /// The same number with the smallest possible, positive denominator.
+    pub fn reduce(self) -> Self {
+        if self.0 == 0 {
+            Rational(0, 1)
+        } else {
+            let l = gcd(self.0, self.1);
+            Rational(self.1.signum() * self.0 / l, self.1.abs() / l)
+        }
+    }
+
+    /// The number as integer, if it is one.
+    pub fn to_integer(self) -> Option<i128> {
+        let r = self.reduce();
+        if r.1 == 1 {
+            Some(r.0)
+        } else {
+            None
+        }
+    }
+
+    /// The numerator, as given, not necessarily reduced.
+    pub fn numerator(self) -> i128 {
+        self.0
+    }
+
+    /// The denominator, as given, not necessarily reduced.
+    pub fn denominator(self) -> i128 {
+        self.1
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        // with the least common denominator, to keep the numbers small
+        let d = gcd(self.1, other.1);
+        let denominator = (self.1 / d).checked_mul(other.1)?;
+        let numerator = self
+            .0
+            .checked_mul(other.1 / d)?
+            .checked_add(other.0.checked_mul(self.1 / d)?)?;
+        checked_reduced(numerator, denominator)
+    }
+
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Rational(self.0.checked_neg()?, self.1))
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_add(other.checked_neg()?)
+    }
+
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        // reduce crosswise first, to keep the numbers small
+        let a = gcd(self.0, other.1);
+        let b = gcd(other.0, self.1);
+        checked_reduced(
+            (self.0 / a).checked_mul(other.0 / b)?,
+            (self.1 / b).checked_mul(other.1 / a)?,
+        )
+    }
+
+    /// The quotient, `None` also for a division by 0.
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        self.checked_mul(checked_reduced(other.1, other.0)?)
+    }
+}
+
+// the reduced fraction with a positive denominator, `None` if the denominator is 0 or it can not
+// be represented
+fn checked_reduced(numerator: i128, denominator: i128) -> Option<Rational> {
+    if denominator == 0 {
+        return None;
+    }
+    let d = gcd(numerator, denominator);
+    let sign = denominator.signum();
+    Some(Rational(
+        (numerator / d).checked_mul(sign)?,
+        (denominator / d).checked_mul(sign)?,
+    ))
+}
+
+impl PartialEq<Self> for Rational {
+    fn eq(&self, other: &Self) -> bool {
+        let r1 = self.reduce();
+        let r2 = other.reduce();
+        r1.0 == r2.0 && r1.1 == r2.1
+    }
+}
+
+impl Eq for Rational {}
+
+impl Hash for Rational {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // equal numbers have the same reduced form
+        let reduced = self.reduce();
+        reduced.0.hash(state);
+        reduced.1.hash(state);
+    }
+}
+
+impl PartialEq<i128> for Rational {
+    fn eq(&self, other: &i128) -> bool {
+        let reduced = self.reduce();
+        reduced.1 == 1 && reduced.0 == *other
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        // with positive denominators, so multiplying with them keeps the order
+        let Rational(lnum, lre) = self.reduce();
+        let Rational(rnum, rre) = rhs.reduce();
+        let lcm = lcm(lre, rre);
+        (lnum * lcm / lre).cmp(&(rnum * lcm / rre))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl ops::Add<Rational> for Rational {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        let de = lcm(self.1, other.1);
+        let num = self.0 * (de / self.1) + other.0 * (de / other.1);
+        Rational(num, de)
+    }
+}
+
+impl ops::Neg for Rational {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Rational(-self.0, self.1)
+    }
+}
+
+impl ops::Sub for Rational {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl ops::Mul for Rational {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        let num = self.0 * other.0;
+        let de = self.1 * other.1;
+        Rational(num, de).reduce()
+    }
+}
+
+impl ops::Div for Rational {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        if other.0 == 0 {
+            panic!("rational division by zero");
+        }
+        self * Rational(other.1, other.0)
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(n: i128) -> Self {
+        Rational(n, 1)
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        let r = self.reduce();
+        if r.1 == 1 {
+            write!(f, "{}", r.0)
+        } else {
+            write!(f, "{}/{}", r.0, r.1)
+        }
+    }
+}
+
+impl fmt::Debug for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+// the greatest common divisor, positive and 1 if both are 0
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.max(1) as i128
+}
+
+fn lcm(a: i128, b: i128) -> i128 {
+    (a * b) / gcd(a, b)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn operators_compare_by_value() {
+        // given
+        let half = Rational::new(1, 2);
+        let third = Rational::new(-2, -6);
+
+        // then
+        assert_eq!(half + third, Rational::new(5, 6));
+        assert_eq!(half * third, Rational::new(2, 12));
+        assert_eq!(half / third, Rational::new(3, 2));
+        assert_eq!((half - third).to_string(), "1/6");
+        assert!(third < half);
+        assert_eq!(Rational::new(4, 2), 2);
+    }
+
+    #[test]
+    fn checked_operations_reduce_and_detect_overflows() {
+        // given
+        let half = Rational::new(1, 2);
+        let third = Rational::new(2, -6);
+
+        // then
+        let sum = half.checked_add(third).expect("expected no overflow");
+        assert_eq!((sum.numerator(), sum.denominator()), (1, 6));
+        assert_eq!(half.checked_div(Rational::ZERO), None);
+        assert_eq!(
+            Rational::integer(i128::MAX).checked_add(Rational::ONE),
+            None
+        );
+        assert_eq!(
+            Rational::integer(i128::MAX).checked_mul(half),
+            Some(Rational::new(i128::MAX, 2))
+        );
+    }
+}
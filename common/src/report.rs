@@ -0,0 +1,56 @@
+use crate::{Answer, Solution};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TimedAnswer {
+    pub answer: Answer,
+    pub time: Duration,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Report {
+    pub day: u8,
+    pub parse_time: Duration,
+    // if the input could not be parsed, there are no answers, only the parse error
+    pub parts: Result<[TimedAnswer; 2], String>,
+}
+
+impl Report {
+    pub fn error(&self) -> Option<&str> {
+        self.parts.as_ref().err().map(|e| e.as_str())
+    }
+}
+
+pub fn solve_timed<S: Solution>(input: &str) -> Report {
+    let start = Instant::now();
+    let parsed = S::parse(input);
+    let parse_time = start.elapsed();
+
+    let parts = parsed.map(|parsed| {
+        let start = Instant::now();
+        let part_one = S::part_one(&parsed);
+        let part_one_time = start.elapsed();
+
+        let start = Instant::now();
+        let part_two = S::part_two(&parsed);
+        let part_two_time = start.elapsed();
+
+        [
+            TimedAnswer {
+                answer: part_one,
+                time: part_one_time,
+            },
+            TimedAnswer {
+                answer: part_two,
+                time: part_two_time,
+            },
+        ]
+    });
+    let parts = parts.map_err(|e| e.to_string());
+
+    Report {
+        day: S::DAY,
+        parse_time,
+        parts,
+    }
+}
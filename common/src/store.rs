@@ -0,0 +1,217 @@
+use crate::STDIN;
+use std::fs::{create_dir_all, read_dir, read_to_string, write};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+pub const YEAR: u16 = 2023;
+// directory of the input store, relative to the working directory, if no other one is given
+pub const DEFAULT_STORE: &str = "inputs";
+// the variant for the own puzzle input, other variants are e.g. examples or inputs of other people
+pub const DEFAULT_VARIANT: &str = "default";
+
+// A local store for puzzle inputs. Each input is stored as `<root>/<year>/<day>/<variant>.txt`,
+// so it can be found by day and variant name instead of by path.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InputStore {
+    root: PathBuf,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct StoredInput {
+    pub day: u8,
+    pub variant: String,
+    pub path: PathBuf,
+}
+
+impl Default for InputStore {
+    fn default() -> Self {
+        InputStore::new(DEFAULT_STORE)
+    }
+}
+
+impl InputStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        InputStore { root: root.into() }
+    }
+
+    // the directory containing the inputs of all days of this year
+    pub fn directory(&self) -> PathBuf {
+        self.root.join(YEAR.to_string())
+    }
+
+    pub fn path(&self, day: u8, variant: &str) -> Result<PathBuf, String> {
+        check_variant(variant)?;
+        Ok(self
+            .directory()
+            .join(format!("{day:02}"))
+            .join(format!("{variant}.txt")))
+    }
+
+    // The input to use if none is given: the default variant, unless something is piped to stdin.
+    pub fn default_input(&self, day: u8, stdin_is_terminal: bool) -> PathBuf {
+        if stdin_is_terminal {
+            self.directory()
+                .join(format!("{day:02}"))
+                .join(format!("{DEFAULT_VARIANT}.txt"))
+        } else {
+            PathBuf::from(STDIN)
+        }
+    }
+
+    // Stores the content as the given variant. An existing input with a different content is
+    // only replaced if `force` is set.
+    pub fn import(
+        &self,
+        day: u8,
+        variant: &str,
+        content: &str,
+        force: bool,
+    ) -> Result<PathBuf, String> {
+        let path = self.path(day, variant)?;
+        match read_to_string(&path) {
+            Ok(existing) if existing != content && !force => {
+                return Err(format!(
+                    "there is already a different input at '{}'",
+                    path.display()
+                ));
+            }
+            Ok(_) => {}
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("unable to read '{}': {e}", path.display())),
+        }
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)
+                .map_err(|e| format!("unable to create directory '{}': {e}", parent.display()))?;
+        }
+        write(&path, content).map_err(|e| format!("unable to write '{}': {e}", path.display()))?;
+        Ok(path)
+    }
+
+    // all stored inputs, ordered by day and variant; files that do not fit the layout are ignored
+    pub fn list(&self) -> Result<Vec<StoredInput>, String> {
+        let directory = self.directory();
+        let mut inputs: Vec<StoredInput> = Vec::with_capacity(64);
+        for day_dir in entries(&directory)? {
+            let Some(day) = day_dir
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.parse::<u8>().ok())
+            else {
+                continue;
+            };
+            for path in entries(&day_dir)? {
+                let variant = path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .and_then(|name| name.strip_suffix(".txt"))
+                    .filter(|variant| check_variant(variant).is_ok());
+                if let Some(variant) = variant {
+                    inputs.push(StoredInput {
+                        day,
+                        variant: variant.to_owned(),
+                        path: path.clone(),
+                    });
+                }
+            }
+        }
+        inputs.sort_unstable_by(|l, r| (l.day, &l.variant).cmp(&(r.day, &r.variant)));
+        Ok(inputs)
+    }
+}
+
+// a missing directory has no entries
+fn entries(directory: &Path) -> Result<Vec<PathBuf>, String> {
+    match read_dir(directory) {
+        Ok(entries) => entries
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("unable to read directory '{}': {e}", directory.display())),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(format!(
+            "unable to read directory '{}': {e}",
+            directory.display()
+        )),
+    }
+}
+
+// variant names end up in file names, so only allow a safe set of characters
+pub fn check_variant(variant: &str) -> Result<(), String> {
+    if !variant.is_empty()
+        && variant
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid variant name '{variant}', only letters, digits, '-' and '_' are allowed"
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env::temp_dir;
+    use std::fs::remove_dir_all;
+
+    fn test_store(name: &str) -> InputStore {
+        let root = temp_dir().join(format!("aoc-store-test-{name}-{}", std::process::id()));
+        let _ = remove_dir_all(&root);
+        InputStore::new(root)
+    }
+
+    #[test]
+    fn path_works() {
+        // given
+        let store = InputStore::new("inputs");
+
+        // then
+        assert_eq!(
+            store.path(7, "alt-user"),
+            Ok(PathBuf::from("inputs/2023/07/alt-user.txt"))
+        );
+        assert!(store.path(7, "../../secret").is_err());
+        assert!(store.path(7, "").is_err());
+    }
+
+    #[test]
+    fn import_and_list_work() {
+        // given
+        let store = test_store("import");
+
+        // when
+        let imported = store.import(12, "example1", "???.### 1,1,3\n", false);
+        let same_again = store.import(12, "example1", "???.### 1,1,3\n", false);
+        let different = store.import(12, "example1", "#.#.### 1,1,3\n", false);
+        let forced = store.import(12, "example1", "#.#.### 1,1,3\n", true);
+        let other_day = store.import(3, DEFAULT_VARIANT, "467..114..\n", false);
+        let list = store.list();
+        let _ = remove_dir_all(store.root);
+
+        // then
+        assert!(imported.is_ok());
+        assert!(same_again.is_ok());
+        assert!(different.is_err());
+        assert!(forced.is_ok());
+        assert!(other_day.is_ok());
+        let list = list.expect("expected listing to work");
+        let days_and_variants: Vec<(u8, &str)> = list
+            .iter()
+            .map(|input| (input.day, input.variant.as_str()))
+            .collect();
+        assert_eq!(days_and_variants, vec![(3, "default"), (12, "example1")]);
+    }
+
+    #[test]
+    fn list_works_for_missing_store() {
+        // given
+        let store = test_store("missing");
+
+        // when
+        let list = store.list();
+
+        // then
+        assert_eq!(list, Ok(vec![]));
+    }
+}
@@ -0,0 +1,136 @@
+//! Day 1: Trebuchet?!
+//!
+//! The calibration value of a line is made of its first and its last digit. Which words count as
+//! digits as well is configured with a [`Vocabulary`].
+
+use common::{parse_lines, Answer, ErrorKind, ParseError, Solution};
+
+mod mode;
+mod stream;
+mod vocabulary;
+pub use mode::StreamMode;
+pub use stream::{stream_calibrations, StreamingCalibration};
+pub use vocabulary::{Calibration, Language, Token, Vocabulary};
+
+/// Solves both parts of the puzzle, see [`Solution`].
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 1;
+
+    type Input<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok(input)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Answer {
+        calibration_sum(input, &Vocabulary::digits()).into()
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Answer {
+        calibration_sum(input, &Vocabulary::language(Language::English)).into()
+    }
+}
+
+/// Sums up the calibration values of all lines, with the numbers the vocabulary recognises.
+///
+/// A line without any digit has no calibration value, which is an error.
+pub fn calibration_sum(input: &str, vocabulary: &Vocabulary) -> Result<u64, ParseError> {
+    let values = calibration_trace(input, vocabulary)?;
+    // every value is below 100, so the sum can not overflow a u64
+    Ok(values
+        .iter()
+        .map(|calibration| u64::from(calibration.value()))
+        .sum())
+}
+
+/// The calibration of every line with the tokens it is made of, e.g. to find out why a line has an
+/// unexpected value.
+pub fn calibration_trace<'a>(
+    input: &'a str,
+    vocabulary: &'a Vocabulary,
+) -> Result<Vec<Calibration<'a>>, ParseError> {
+    parse_lines(input, |line| {
+        vocabulary
+            .calibrate(line)
+            .ok_or_else(|| ParseError::at(line, line, ErrorKind::Missing("digit")))
+    })
+}
+
+/// The calibration value of a line, counting only the digits `0` to `9` (part one).
+pub fn bad_calibration(line: &str) -> Option<u32> {
+    Vocabulary::digits().calibrate(line).map(|c| c.value())
+}
+
+/// The calibration value of a line, counting digits spelled out as English words as well (part
+/// two).
+pub fn full_calibration(line: &str) -> Option<u32> {
+    Vocabulary::language(Language::English)
+        .calibrate(line)
+        .map(|c| c.value())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bad_calibration() {
+        assert_eq!(bad_calibration("1abc2"), Some(12));
+        assert_eq!(bad_calibration("pqr3stu8vwx"), Some(38));
+        assert_eq!(bad_calibration("a1b2c3d4e5f"), Some(15));
+        assert_eq!(bad_calibration("treb7uchet"), Some(77));
+        assert_eq!(bad_calibration("eightwothree"), None);
+    }
+
+    #[test]
+    fn test_full_calibration() {
+        assert_eq!(full_calibration("two1nine"), Some(29));
+        assert_eq!(full_calibration("eightwothree"), Some(83));
+        assert_eq!(full_calibration("abcone2threexyz"), Some(13));
+        assert_eq!(full_calibration("xtwone3four"), Some(24));
+        assert_eq!(full_calibration("4nineeightseven2"), Some(42));
+        assert_eq!(full_calibration("zoneight234"), Some(14));
+        assert_eq!(full_calibration("7pqrstsixteen"), Some(76));
+        assert_eq!(full_calibration("eighthree"), Some(83));
+        assert_eq!(full_calibration("sevenine"), Some(79));
+    }
+
+    #[test]
+    fn puzzle_works_for_examples() {
+        // given
+        let example_one = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n";
+        let example_two = "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen\n";
+
+        // when
+        let (part_one, _) =
+            common::solve::<Puzzle>(example_one).expect("expected successful parsing");
+        let (_, part_two) =
+            common::solve::<Puzzle>(example_two).expect("expected successful parsing");
+
+        // then
+        assert_eq!(part_one, Answer::Integer(142));
+        assert_eq!(part_two, Answer::Integer(281));
+    }
+
+    #[test]
+    fn calibration_trace_works_for_example() {
+        // given
+        let input = "two1nine\neightwothree\n";
+        let vocabulary = Vocabulary::language(Language::English);
+
+        // when
+        let trace = calibration_trace(input, &vocabulary).expect("expected calibration values");
+
+        // then
+        let trace: Vec<String> = trace.iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            trace,
+            vec![
+                "29 (first 'two' at 0, last 'nine' at 4)",
+                "83 (first 'eight' at 0, last 'three' at 7)"
+            ]
+        );
+    }
+}
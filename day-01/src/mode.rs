@@ -0,0 +1,105 @@
+use crate::{stream_calibrations, Language, StreamingCalibration, Vocabulary};
+use common::{Answer, Mode, STDIN};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::time::Instant;
+
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// The `--stream` mode of the binary: solves both parts in one pass over each input, without
+/// reading the whole input into memory.
+pub struct StreamMode {
+    chunk_size: usize,
+    // report the running sums after this many lines
+    progress: Option<usize>,
+    digits: Vocabulary,
+    words: Vocabulary,
+}
+
+impl Default for StreamMode {
+    fn default() -> Self {
+        StreamMode {
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            progress: None,
+            digits: Vocabulary::digits(),
+            words: Vocabulary::language(Language::English),
+        }
+    }
+}
+
+fn positive_value(args: &mut dyn Iterator<Item = &String>, option: &str) -> Result<usize, String> {
+    args.next()
+        .and_then(|value| value.parse::<usize>().ok())
+        .filter(|value| *value > 0)
+        .ok_or_else(|| format!("option '{option}' needs a positive number"))
+}
+
+fn running_sum(calibration: &StreamingCalibration<'_>) -> String {
+    match calibration.error() {
+        Some(e) => format!("failed ({e})"),
+        None => calibration.sum().to_string(),
+    }
+}
+
+impl Mode for StreamMode {
+    fn flags(&self) -> &[&str] {
+        &["--stream"]
+    }
+
+    fn usage(&self) -> &str {
+        "--stream [--chunk-size <bytes>] [--progress <lines>]"
+    }
+
+    fn argument(
+        &mut self,
+        arg: &str,
+        args: &mut dyn Iterator<Item = &String>,
+    ) -> Result<bool, String> {
+        match arg {
+            "--stream" => {}
+            "--chunk-size" => self.chunk_size = positive_value(args, arg)?,
+            "--progress" => self.progress = Some(positive_value(args, arg)?),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn run(&mut self, input: &Path, name: &str) -> Result<(), String> {
+        let reader: Box<dyn Read> = if input == Path::new(STDIN) {
+            Box::new(io::stdin().lock())
+        } else {
+            Box::new(
+                File::open(input)
+                    .map_err(|e| format!("unable to open input file '{}': {e}", input.display()))?,
+            )
+        };
+        let mut calibrations = [
+            StreamingCalibration::new(&self.digits),
+            StreamingCalibration::new(&self.words),
+        ];
+        let mut reported = 0;
+        let start = Instant::now();
+        stream_calibrations(reader, &mut calibrations, self.chunk_size, |c| {
+            let lines = c[1].lines();
+            if let Some(every) = self.progress {
+                if lines / every > reported / every {
+                    eprintln!(
+                        "{name}, line {lines}: part one {}, part two {}",
+                        running_sum(&c[0]),
+                        running_sum(&c[1])
+                    );
+                    reported = lines;
+                }
+            }
+        })
+        .map_err(|e| format!("unable to read input: {e}"))?;
+        let duration = start.elapsed();
+
+        let [part_one, part_two] = calibrations.map(|c| Answer::from(c.finish()));
+        println!("Day 1, {name} (streamed in {duration:?})");
+        println!("Part one: {part_one}");
+        println!("Part two: {part_two}");
+        Ok(())
+    }
+}
@@ -0,0 +1,240 @@
+use crate::vocabulary::first_digit;
+use crate::Vocabulary;
+use common::{ErrorKind, ParseError};
+use std::io::{self, ErrorKind as IoErrorKind, Read};
+
+/// Calibrates a document that arrives chunk by chunk, e.g. a file that is too large to be read at
+/// once. Of the current line, only the few bytes needed to find words that continue in the next
+/// chunk are kept, so chunks may end anywhere, even within a word or a character.
+#[derive(Clone, Debug)]
+pub struct StreamingCalibration<'a> {
+    vocabulary: &'a Vocabulary,
+    max_len: usize,
+    // the end of the current line so far
+    tail: Vec<u8>,
+    // the number of bytes of the current line so far
+    line_len: usize,
+    // start, length and value of the first token of the current line so far
+    first: Option<(usize, usize, u32)>,
+    // value of the last token of the current line so far
+    last: Option<u32>,
+    lines: usize,
+    sum: u64,
+    error: Option<ParseError>,
+}
+
+impl<'a> StreamingCalibration<'a> {
+    pub fn new(vocabulary: &'a Vocabulary) -> Self {
+        let max_len = vocabulary.max_len();
+        StreamingCalibration {
+            vocabulary,
+            max_len,
+            tail: Vec::with_capacity(max_len * 2),
+            line_len: 0,
+            first: None,
+            last: None,
+            lines: 0,
+            sum: 0,
+            error: None,
+        }
+    }
+
+    /// Processes the next chunk of the document. After a line without any digit, the rest of the
+    /// document is ignored, see [`StreamingCalibration::error`].
+    pub fn push(&mut self, chunk: &[u8]) {
+        for (i, part) in chunk.split(|b| *b == b'\n').enumerate() {
+            if self.error.is_some() {
+                return;
+            }
+            if i > 0 {
+                self.end_line();
+            }
+            for b in part {
+                self.push_byte(*b);
+            }
+        }
+    }
+
+    fn push_byte(&mut self, b: u8) {
+        self.tail.push(b);
+        self.line_len += 1;
+        // All tokens ending here end after all tokens found so far, so the longest of them is the
+        // new last token. It also starts before all others ending here, so it is the only one that
+        // may be a better first token than the one found so far.
+        let longest = self
+            .vocabulary
+            .matches_ending_at(&self.tail)
+            .max_by_key(|(len, _)| *len);
+        if let Some((len, value)) = longest {
+            let start = self.line_len - len;
+            self.last = Some(value);
+            let better = match self.first {
+                None => true,
+                Some((first_start, first_len, _)) => {
+                    start < first_start || (start == first_start && len > first_len)
+                }
+            };
+            if better {
+                self.first = Some((start, len, value));
+            }
+        }
+        if self.tail.len() >= 2 * self.max_len {
+            self.tail.drain(..self.tail.len() - self.max_len);
+        }
+    }
+
+    fn end_line(&mut self) {
+        self.lines += 1;
+        match (self.first, self.last) {
+            (Some((_, _, first)), Some(last)) => {
+                self.sum += (first_digit(first) * 10 + last % 10) as u64;
+            }
+            _ => {
+                self.error = Some(ParseError {
+                    line: self.lines,
+                    column: 1,
+                    text: String::new(),
+                    kind: ErrorKind::Missing("digit"),
+                });
+            }
+        }
+        self.tail.clear();
+        self.line_len = 0;
+        self.first = None;
+        self.last = None;
+    }
+
+    /// The number of complete lines so far.
+    pub fn lines(&self) -> usize {
+        self.lines
+    }
+
+    /// The sum of the calibration values of all complete lines so far.
+    pub fn sum(&self) -> u64 {
+        self.sum
+    }
+
+    pub fn error(&self) -> Option<&ParseError> {
+        self.error.as_ref()
+    }
+
+    /// Ends the document, a last line does not need a line break.
+    pub fn finish(mut self) -> Result<u64, ParseError> {
+        if self.error.is_none() && self.line_len > 0 {
+            self.end_line();
+        }
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(self.sum),
+        }
+    }
+}
+
+/// Reads everything from `reader` in chunks of `chunk_size` bytes and passes every chunk to all of
+/// the calibrations, so several vocabularies need only one pass. `progress` is called after every
+/// chunk.
+pub fn stream_calibrations(
+    mut reader: impl Read,
+    calibrations: &mut [StreamingCalibration<'_>],
+    chunk_size: usize,
+    mut progress: impl FnMut(&[StreamingCalibration<'_>]),
+) -> io::Result<()> {
+    let mut buffer = vec![0u8; chunk_size.max(1)];
+    loop {
+        let n = match reader.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(n) => n,
+            Err(e) if e.kind() == IoErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        for calibration in calibrations.iter_mut() {
+            calibration.push(&buffer[..n]);
+        }
+        progress(calibrations);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{calibration_sum, Language};
+
+    const EXAMPLE: &str = r#"two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+"#;
+
+    fn streamed_sum(input: &str, vocabulary: &Vocabulary, chunk_size: usize) -> u64 {
+        let mut calibrations = [StreamingCalibration::new(vocabulary)];
+        stream_calibrations(input.as_bytes(), &mut calibrations, chunk_size, |_| {})
+            .expect("expected reading from a slice to work");
+        let [calibration] = calibrations;
+        calibration.finish().expect("expected calibration values")
+    }
+
+    #[test]
+    fn streaming_works_for_all_chunk_sizes() {
+        // given
+        let vocabulary = Vocabulary::language(Language::English);
+
+        // then
+        for chunk_size in 1..=EXAMPLE.len() {
+            assert_eq!(streamed_sum(EXAMPLE, &vocabulary, chunk_size), 281);
+        }
+    }
+
+    #[test]
+    fn streaming_works_for_words_split_within_characters() {
+        // given
+        let input = "xfünfzehnzwei\nachtzehn\nelfünf";
+        let vocabulary = Vocabulary::language_to_twenty(Language::German);
+        let expected = calibration_sum(input, &vocabulary).expect("expected calibration values");
+
+        // then
+        assert_eq!(expected, 12 + 18 + 15);
+        for chunk_size in 1..=input.len() {
+            assert_eq!(streamed_sum(input, &vocabulary, chunk_size), expected);
+        }
+    }
+
+    #[test]
+    fn streaming_reports_running_sums() {
+        // given
+        let vocabulary = Vocabulary::digits();
+        let mut calibrations = [StreamingCalibration::new(&vocabulary)];
+        let mut progress: Vec<(usize, u64)> = Vec::with_capacity(4);
+
+        // when
+        stream_calibrations(
+            "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet".as_bytes(),
+            &mut calibrations,
+            12,
+            |calibrations| progress.push((calibrations[0].lines(), calibrations[0].sum())),
+        )
+        .expect("expected reading from a slice to work");
+
+        // then
+        assert_eq!(progress, vec![(1, 12), (2, 50), (3, 65), (3, 65)]);
+        let [calibration] = calibrations;
+        assert_eq!(calibration.finish(), Ok(142));
+    }
+
+    #[test]
+    fn streaming_reports_line_without_digit() {
+        // given
+        let vocabulary = Vocabulary::digits();
+        let mut calibration = StreamingCalibration::new(&vocabulary);
+
+        // when
+        calibration.push(b"two1nine\neightwo");
+        calibration.push(b"three\n4nineeightseven2\n");
+
+        // then
+        let error = calibration.finish().expect_err("expected a missing digit");
+        assert_eq!(error.to_string(), "line 2, column 1: missing digit");
+    }
+}
@@ -0,0 +1,387 @@
+use std::fmt;
+
+const ENGLISH: [&str; 20] = [
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+    "twenty",
+];
+
+const GERMAN: [&str; 20] = [
+    "eins",
+    "zwei",
+    "drei",
+    "vier",
+    "fünf",
+    "sechs",
+    "sieben",
+    "acht",
+    "neun",
+    "zehn",
+    "elf",
+    "zwölf",
+    "dreizehn",
+    "vierzehn",
+    "fünfzehn",
+    "sechzehn",
+    "siebzehn",
+    "achtzehn",
+    "neunzehn",
+    "zwanzig",
+];
+
+const FRENCH: [&str; 20] = [
+    "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf", "dix", "onze", "douze",
+    "treize", "quatorze", "quinze", "seize", "dix-sept", "dix-huit", "dix-neuf", "vingt",
+];
+
+/// A language with built-in number words.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Language {
+    English,
+    German,
+    French,
+}
+
+impl Language {
+    // the words for the numbers from one to twenty
+    fn words(self) -> &'static [&'static str; 20] {
+        match self {
+            Language::English => &ENGLISH,
+            Language::German => &GERMAN,
+            Language::French => &FRENCH,
+        }
+    }
+}
+
+/// The words that are recognised as numbers in a calibration document, besides the digits `0` to
+/// `9`, which are always recognised.
+///
+/// A word for a number above nine counts as if its digits had been written, e.g. `twelve` starts
+/// with a 1 and ends with a 2.
+#[derive(Clone, PartialEq, Eq, Default, Debug)]
+pub struct Vocabulary {
+    words: Vec<(String, u32)>,
+}
+
+impl Vocabulary {
+    /// Only the digits, without any words (part one).
+    pub fn digits() -> Self {
+        Vocabulary::default()
+    }
+
+    /// The words for one to nine in the given language (part two for English).
+    pub fn language(language: Language) -> Self {
+        Vocabulary::from_words(&language.words()[..9])
+    }
+
+    /// The words for one to twenty in the given language.
+    pub fn language_to_twenty(language: Language) -> Self {
+        Vocabulary::from_words(language.words())
+    }
+
+    fn from_words(words: &[&str]) -> Self {
+        Vocabulary {
+            words: words
+                .iter()
+                .zip(1..)
+                .map(|(word, value)| (word.to_string(), value))
+                .collect(),
+        }
+    }
+
+    /// A custom vocabulary. Words must not be empty.
+    pub fn new<S: Into<String>>(words: impl IntoIterator<Item = (S, u32)>) -> Result<Self, String> {
+        let words: Vec<(String, u32)> = words
+            .into_iter()
+            .map(|(word, value)| (word.into(), value))
+            .collect();
+        if let Some((_, value)) = words.iter().find(|(word, _)| word.is_empty()) {
+            return Err(format!("the word for {value} must not be empty"));
+        }
+        Ok(Vocabulary { words })
+    }
+
+    /// Both the words of this and the other vocabulary, e.g. to recognise several languages.
+    pub fn with(mut self, other: Vocabulary) -> Self {
+        self.words.extend(other.words);
+        self
+    }
+
+    /// The words and the numbers they stand for.
+    pub fn words(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.words
+            .iter()
+            .map(|(word, value)| (word.as_str(), *value))
+    }
+
+    /// The length of the longest word in bytes, at least 1 for the digits.
+    pub fn max_len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|(word, _)| word.len())
+            .max()
+            .unwrap_or(1)
+            .max(1)
+    }
+
+    /// All tokens in the line, ordered by where they start. Tokens may overlap, like `eight` and
+    /// `two` in `eightwo`.
+    pub fn tokens<'a>(&'a self, line: &'a str) -> impl Iterator<Item = Token<'a>> + 'a {
+        line.char_indices()
+            .flat_map(move |(start, _)| self.tokens_starting_at(line, start))
+    }
+
+    fn tokens_starting_at<'a>(
+        &'a self,
+        line: &'a str,
+        start: usize,
+    ) -> impl Iterator<Item = Token<'a>> + 'a {
+        let rest = &line[start..];
+        let digit = rest
+            .chars()
+            .next()
+            .and_then(|c| c.to_digit(10).map(|value| (c.len_utf8(), value)));
+        let words = self
+            .words
+            .iter()
+            .filter(move |(word, _)| rest.starts_with(word.as_str()))
+            .map(|(word, value)| (word.len(), *value));
+        digit
+            .into_iter()
+            .chain(words)
+            .map(move |(len, value)| Token {
+                start,
+                text: &line[start..start + len],
+                value,
+            })
+    }
+
+    fn tokens_ending_at<'a>(
+        &'a self,
+        line: &'a str,
+        end: usize,
+    ) -> impl Iterator<Item = Token<'a>> {
+        self.matches_ending_at(&line.as_bytes()[..end])
+            .map(move |(len, value)| Token {
+                start: end - len,
+                text: &line[end - len..end],
+                value,
+            })
+    }
+
+    // The length and value of every token at the end of `before`. This works on bytes, so it can
+    // be used on chunks of input that split characters. A word can only match at a character
+    // boundary, because it is valid UTF-8 itself.
+    pub(crate) fn matches_ending_at<'a>(
+        &'a self,
+        before: &'a [u8],
+    ) -> impl Iterator<Item = (usize, u32)> + 'a {
+        let digit = before
+            .last()
+            .filter(|b| b.is_ascii_digit())
+            .map(|b| (1, (b - b'0') as u32));
+        let words = self
+            .words
+            .iter()
+            .filter(move |(word, _)| before.ends_with(word.as_bytes()))
+            .map(|(word, value)| (word.len(), *value));
+        digit.into_iter().chain(words)
+    }
+
+    /// The token that starts first. If several tokens start there, the longest one is used.
+    pub fn first_token<'a>(&'a self, line: &'a str) -> Option<Token<'a>> {
+        line.char_indices().find_map(|(start, _)| {
+            self.tokens_starting_at(line, start)
+                .max_by_key(|token| token.text.len())
+        })
+    }
+
+    /// The token that ends last. If several tokens end there, the longest one is used.
+    pub fn last_token<'a>(&'a self, line: &'a str) -> Option<Token<'a>> {
+        (1..=line.len())
+            .rev()
+            .filter(|end| line.is_char_boundary(*end))
+            .find_map(|end| {
+                self.tokens_ending_at(line, end)
+                    .max_by_key(|token| token.text.len())
+            })
+    }
+
+    /// The calibration value of a line and the tokens it is made of, `None` if there is no token.
+    pub fn calibrate<'a>(&'a self, line: &'a str) -> Option<Calibration<'a>> {
+        Some(Calibration {
+            first: self.first_token(line)?,
+            last: self.last_token(line)?,
+        })
+    }
+}
+
+/// A digit or word in a line that stands for a number.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Token<'a> {
+    /// The byte offset in the line.
+    pub start: usize,
+    pub text: &'a str,
+    pub value: u32,
+}
+
+impl Token<'_> {
+    pub fn first_digit(&self) -> u32 {
+        first_digit(self.value)
+    }
+
+    pub fn last_digit(&self) -> u32 {
+        self.value % 10
+    }
+}
+
+pub(crate) fn first_digit(mut value: u32) -> u32 {
+    while value >= 10 {
+        value /= 10;
+    }
+    value
+}
+
+/// The calibration value of a line, with the tokens that produced it.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Calibration<'a> {
+    pub first: Token<'a>,
+    pub last: Token<'a>,
+}
+
+impl Calibration<'_> {
+    pub fn value(&self) -> u32 {
+        self.first.first_digit() * 10 + self.last.last_digit()
+    }
+}
+
+impl fmt::Display for Calibration<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(
+            f,
+            "{} (first '{}' at {}, last '{}' at {})",
+            self.value(),
+            self.first.text,
+            self.first.start,
+            self.last.text,
+            self.last.start
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn value(vocabulary: &Vocabulary, line: &str) -> Option<u32> {
+        vocabulary.calibrate(line).map(|c| c.value())
+    }
+
+    #[test]
+    fn calibrate_works_for_overlapping_words() {
+        // given
+        let english = Vocabulary::language(Language::English);
+
+        // then
+        assert_eq!(value(&english, "eightwo"), Some(82));
+        assert_eq!(value(&english, "xtwone3four"), Some(24));
+        assert_eq!(value(&english, "oneight"), Some(18));
+        assert_eq!(value(&english, "abc"), None);
+        assert_eq!(value(&Vocabulary::digits(), "eightwo"), None);
+    }
+
+    #[test]
+    fn calibrate_works_for_other_languages() {
+        // given
+        let german = Vocabulary::language(Language::German);
+        let french = Vocabulary::language(Language::French);
+        let both = german.clone().with(french.clone());
+
+        // then
+        assert_eq!(value(&german, "xfünfzweiy"), Some(52));
+        assert_eq!(value(&german, "achtwo"), Some(88));
+        assert_eq!(value(&french, "huitrois"), Some(83));
+        assert_eq!(value(&both, "neuf7neun"), Some(99));
+        assert_eq!(value(&both, "dreiseptacht"), Some(38));
+    }
+
+    #[test]
+    fn calibrate_works_up_to_twenty() {
+        // given
+        let english = Vocabulary::language_to_twenty(Language::English);
+        let german = Vocabulary::language_to_twenty(Language::German);
+        let french = Vocabulary::language_to_twenty(Language::French);
+
+        // then
+        assert_eq!(value(&english, "seventeen"), Some(17));
+        assert_eq!(value(&english, "twelve3eighteen"), Some(18));
+        assert_eq!(value(&english, "ninetwenty"), Some(90));
+        assert_eq!(value(&german, "dreizehn"), Some(13));
+        assert_eq!(value(&french, "quatre-dix-sept"), Some(47));
+    }
+
+    #[test]
+    fn calibrate_reports_tokens() {
+        // given
+        let english = Vocabulary::language(Language::English);
+
+        // when
+        let calibration = english
+            .calibrate("zoneight234")
+            .expect("expected a calibration value");
+
+        // then
+        assert_eq!(
+            calibration.first,
+            Token {
+                start: 1,
+                text: "one",
+                value: 1
+            }
+        );
+        assert_eq!(
+            calibration.last,
+            Token {
+                start: 10,
+                text: "4",
+                value: 4
+            }
+        );
+        assert_eq!(
+            calibration.to_string(),
+            "14 (first 'one' at 1, last '4' at 10)"
+        );
+    }
+
+    #[test]
+    fn tokens_include_overlapping_words() {
+        // given
+        let vocabulary = Vocabulary::new([("ab", 7), ("bc", 5), ("abc", 12)])
+            .expect("expected a valid vocabulary");
+
+        // when
+        let tokens: Vec<&str> = vocabulary.tokens("xabc1").map(|t| t.text).collect();
+
+        // then
+        assert_eq!(tokens, vec!["ab", "abc", "bc", "1"]);
+        assert_eq!(value(&vocabulary, "abc"), Some(12));
+        assert!(Vocabulary::new([("", 1)]).is_err());
+    }
+}
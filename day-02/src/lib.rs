@@ -0,0 +1,344 @@
+//! Day 2: Cube Conundrum
+//!
+//! Games of drawing cubes out of a bag, and what the bag could have contained.
+
+use common::{parse_lines, parse_number, split_once, Answer, ErrorKind, ParseError, Solution};
+use std::collections::BTreeMap;
+use std::fmt;
+
+mod mode;
+pub use mode::QueryMode;
+
+/// Solves both parts of the puzzle, see [`Solution`].
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 2;
+
+    type Input<'a> = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_lines(input, parse_game)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Answer {
+        possible_games(input).into()
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Answer {
+        minimal_cubes(input).into()
+    }
+}
+
+/// The colors of the puzzle, whose counts are multiplied for the power of a bag.
+pub const STANDARD_COLORS: [&str; 3] = ["red", "green", "blue"];
+
+/// The bag of part one: 12 red, 13 green and 14 blue cubes.
+pub fn standard_bag() -> Cubes {
+    Cubes::default()
+        .with("red", 12)
+        .with("green", 13)
+        .with("blue", 14)
+}
+
+/// A number of cubes of each color, either shown at once in a game or contained in a bag. Colors
+/// are not limited to red, green and blue.
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct Cubes {
+    // colors without cubes are not stored
+    counts: BTreeMap<String, u32>,
+}
+
+impl Cubes {
+    /// These cubes and `count` more of the given color.
+    ///
+    /// Panics if there would be more than `u32::MAX` cubes of the color.
+    pub fn with(mut self, color: impl Into<String>, count: u32) -> Self {
+        let color = color.into();
+        if !self.add(&color, count) {
+            panic!("too many {color} cubes");
+        }
+        self
+    }
+
+    /// Adds `count` cubes of the given color. Returns false, without adding any, if there would be
+    /// more than `u32::MAX` cubes of the color.
+    pub fn add(&mut self, color: impl Into<String>, count: u32) -> bool {
+        if count == 0 {
+            return true;
+        }
+        let own = self.counts.entry(color.into()).or_insert(0);
+        match own.checked_add(count) {
+            Some(sum) => {
+                *own = sum;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn count(&self, color: &str) -> u32 {
+        self.counts.get(color).copied().unwrap_or(0)
+    }
+
+    /// The colors with at least one cube and their counts, ordered by color.
+    pub fn colors(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.counts
+            .iter()
+            .map(|(color, count)| (color.as_str(), *count))
+    }
+
+    /// Whether all of the other cubes can be taken from these at once.
+    pub fn contains(&self, other: &Cubes) -> bool {
+        other
+            .colors()
+            .all(|(color, count)| count <= self.count(color))
+    }
+
+    /// The smallest cubes that contain both these and the other cubes.
+    pub fn union(mut self, other: &Cubes) -> Self {
+        for (color, count) in other.colors() {
+            let own = self.counts.entry(color.to_owned()).or_insert(0);
+            *own = (*own).max(count);
+        }
+        self
+    }
+}
+
+impl fmt::Display for Cubes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        if self.counts.is_empty() {
+            return write!(f, "no cubes");
+        }
+        for (i, (color, count)) in self.colors().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{count} {color}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A game with its ID and all selections of cubes shown in it.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Game {
+    pub id: u32,
+    pub selections: Vec<Cubes>,
+}
+
+impl Game {
+    /// Whether every selection of the game can be taken from the bag.
+    pub fn is_possible(&self, bag: &Cubes) -> bool {
+        self.selections
+            .iter()
+            .all(|selection| bag.contains(selection))
+    }
+
+    /// The smallest bag the game is possible with.
+    pub fn minimal_bag(&self) -> Cubes {
+        self.selections
+            .iter()
+            .fold(Cubes::default(), |min, selection| min.union(selection))
+    }
+
+    /// The colors of which removing a single cube from the bag makes the game impossible. If the
+    /// game is not possible with the bag in the first place, there are none.
+    pub fn critical_colors<'a>(&self, bag: &'a Cubes) -> Vec<&'a str> {
+        if !self.is_possible(bag) {
+            return vec![];
+        }
+        let minimal = self.minimal_bag();
+        bag.colors()
+            .filter(|(color, count)| minimal.count(color) == *count)
+            .map(|(color, _)| color)
+            .collect()
+    }
+}
+
+/// Parses a game from a line like `Game 1: 3 blue, 4 red; 1 red, 2 green`.
+pub fn parse_game(line: &str) -> Result<Game, ParseError> {
+    let (game_id, selections) =
+        split_once(line, line, ": ", "separator between game ID and selections")?;
+    let game_id = game_id
+        .strip_prefix("Game ")
+        .ok_or_else(|| ParseError::at(line, game_id, ErrorKind::Missing("prefix 'Game '")))?;
+    let game_id: u32 = parse_number(line, game_id)?;
+
+    let selections = selections
+        .split("; ")
+        .map(|selection| parse_selection(line, selection))
+        .collect::<Result<Vec<Cubes>, ParseError>>()?;
+
+    Ok(Game {
+        id: game_id,
+        selections,
+    })
+}
+
+/// Parses cubes like `12 red, 13 green, 14 blue`, e.g. the contents of a bag.
+pub fn parse_cubes(cubes: &str) -> Result<Cubes, ParseError> {
+    parse_selection(cubes, cubes)
+}
+
+fn parse_selection(line: &str, selection_str: &str) -> Result<Cubes, ParseError> {
+    let mut selection = Cubes::default();
+    // counts of zero are not stored, so the colors are tracked separately
+    let mut colors: Vec<&str> = Vec::new();
+    for cubes in selection_str.split(", ") {
+        let (num, color) = split_once(line, cubes, " ", "separator between color and number")?;
+        let num: u32 = parse_number(line, num)?;
+        if color.is_empty() || !color.chars().all(|c| c.is_alphabetic()) {
+            let kind = ErrorKind::Invalid("a color must be a single word".to_owned());
+            return Err(ParseError::at(line, color, kind));
+        }
+        if colors.contains(&color) {
+            let kind = ErrorKind::Invalid(format!("color '{color}' is shown twice"));
+            return Err(ParseError::at(line, color, kind));
+        }
+        colors.push(color);
+        selection.add(color, num);
+    }
+    Ok(selection)
+}
+
+/// The IDs of the games that are possible with the bag.
+pub fn possible_games_with(games: &[Game], bag: &Cubes) -> Vec<u32> {
+    games
+        .iter()
+        .filter(|game| game.is_possible(bag))
+        .map(|game| game.id)
+        .collect()
+}
+
+/// Sums up the IDs of the games that are possible with 12 red, 13 green and 14 blue cubes.
+pub fn possible_games(games: &[Game]) -> u32 {
+    possible_games_with(games, &standard_bag()).iter().sum()
+}
+
+/// Sums up the power (product of the red, green and blue cubes) of the smallest possible bag of
+/// every game.
+pub fn minimal_cubes(games: &[Game]) -> u32 {
+    games
+        .iter()
+        .map(|game| {
+            let min = game.minimal_bag();
+            STANDARD_COLORS
+                .iter()
+                .map(|color| min.count(color))
+                .product::<u32>()
+        })
+        .sum::<u32>()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = r#"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+"#;
+
+    #[test]
+    fn puzzle_works_for_example() {
+        // given
+        let games = Puzzle::parse(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let part_one = Puzzle::part_one(&games);
+        let part_two = Puzzle::part_two(&games);
+
+        // then
+        assert_eq!(part_one, Answer::Integer(8));
+        assert_eq!(part_two, Answer::Integer(2286));
+    }
+
+    #[test]
+    fn parse_reports_position_of_invalid_color() {
+        // given
+        let input = "Game 1: 3 blue, 4 red\nGame 2: 1 blue; 2 purple!\n";
+
+        // when
+        let error = Puzzle::parse(input).expect_err("expected parsing to fail");
+
+        // then
+        assert_eq!((error.line, error.column), (2, 19));
+        assert_eq!(error.text, "purple!");
+        assert_eq!(
+            error.kind,
+            ErrorKind::Invalid("a color must be a single word".to_owned())
+        );
+    }
+
+    #[test]
+    fn parse_rejects_colors_shown_twice() {
+        // given
+        let input = "Game 1: 3 blue, 4 red\nGame 2: 0 red, 1 blue, 2 red\n";
+
+        // when
+        let error = Puzzle::parse(input).expect_err("expected parsing to fail");
+
+        // then
+        assert_eq!((error.line, error.column), (2, 26));
+        assert_eq!(error.text, "red");
+        assert_eq!(
+            error.kind,
+            ErrorKind::Invalid("color 'red' is shown twice".to_owned())
+        );
+    }
+
+    #[test]
+    fn add_rejects_too_many_cubes() {
+        // given
+        let mut cubes = Cubes::default().with("red", u32::MAX - 1);
+
+        // then
+        assert!(cubes.add("red", 1));
+        assert!(!cubes.add("red", 1));
+        assert_eq!(cubes.count("red"), u32::MAX);
+    }
+
+    #[test]
+    fn queries_work_for_other_colors() {
+        // given
+        let input = "Game 1: 3 blue, 4 purple; 1 red\nGame 2: 2 red, 5 purple\nGame 3: 1 blue\n";
+        let games = Puzzle::parse(input).expect("expected successful parsing");
+        let bag = parse_cubes("4 purple, 1 red, 3 blue").expect("expected a valid bag");
+
+        // when
+        let possible = possible_games_with(&games, &bag);
+        let minimal: Vec<String> = games.iter().map(|g| g.minimal_bag().to_string()).collect();
+
+        // then
+        assert_eq!(possible, vec![1, 3]);
+        assert_eq!(
+            minimal,
+            vec!["3 blue, 4 purple, 1 red", "5 purple, 2 red", "1 blue"]
+        );
+    }
+
+    #[test]
+    fn critical_colors_work_for_example() {
+        // given
+        let games = Puzzle::parse(EXAMPLE).expect("expected successful parsing");
+        let bag = parse_cubes("6 red, 3 green, 6 blue").expect("expected a valid bag");
+
+        // when
+        let critical: Vec<Vec<&str>> = games.iter().map(|g| g.critical_colors(&bag)).collect();
+
+        // then
+        assert_eq!(
+            critical,
+            vec![
+                vec!["blue"],
+                vec!["green"],
+                vec![],
+                vec![],
+                vec!["green", "red"]
+            ]
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{parse_cubes, possible_games_with, standard_bag, Cubes, Game, Puzzle};
+use common::{option_value, read_input, Mode, Solution};
+use std::path::Path;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Query {
+    // the games that are possible with the bag
+    Possible,
+    // the smallest bag for each game
+    Minimal,
+    // the colors of which one cube less in the bag makes a game impossible
+    Critical,
+}
+
+/// The `--query` mode of the binary: answers questions about the games of each input with any bag
+/// and colors, e.g. `--query possible --bag '12 red, 13 green, 14 blue, 3 purple'`.
+pub struct QueryMode {
+    query: Query,
+    bag: Cubes,
+    // only answer the query for this game
+    game: Option<u32>,
+}
+
+impl Default for QueryMode {
+    fn default() -> Self {
+        QueryMode {
+            query: Query::Possible,
+            bag: standard_bag(),
+            game: None,
+        }
+    }
+}
+
+impl QueryMode {
+    fn answer(&self, games: &[Game]) -> Result<(), String> {
+        let games: Vec<Game> = match self.game {
+            Some(id) => {
+                let game = games
+                    .iter()
+                    .find(|game| game.id == id)
+                    .ok_or_else(|| format!("there is no game {id}"))?;
+                vec![game.clone()]
+            }
+            None => games.to_vec(),
+        };
+        match self.query {
+            Query::Possible => {
+                let possible = possible_games_with(&games, &self.bag);
+                let ids: Vec<String> = possible.iter().map(|id| id.to_string()).collect();
+                println!(
+                    "possible games: {} (sum of IDs: {})",
+                    ids.join(", "),
+                    possible.iter().sum::<u32>()
+                );
+            }
+            Query::Minimal => {
+                for game in &games {
+                    println!("Game {}: {}", game.id, game.minimal_bag());
+                }
+            }
+            Query::Critical => {
+                for game in &games {
+                    if !game.is_possible(&self.bag) {
+                        println!("Game {}: already impossible", game.id);
+                        continue;
+                    }
+                    let colors = game.critical_colors(&self.bag);
+                    if colors.is_empty() {
+                        println!("Game {}: stays possible without any single cube", game.id);
+                    } else {
+                        println!(
+                            "Game {}: impossible without one cube of {}",
+                            game.id,
+                            colors.join(" or ")
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Mode for QueryMode {
+    fn flags(&self) -> &[&str] {
+        &["--query"]
+    }
+
+    fn usage(&self) -> &str {
+        "--query possible|minimal|critical [--bag <cubes>] [--game <id>]"
+    }
+
+    fn argument(
+        &mut self,
+        arg: &str,
+        args: &mut dyn Iterator<Item = &String>,
+    ) -> Result<bool, String> {
+        match arg {
+            "--query" => {
+                self.query = match option_value(args, arg)? {
+                    "possible" => Query::Possible,
+                    "minimal" => Query::Minimal,
+                    "critical" => Query::Critical,
+                    other => return Err(format!("unknown query '{other}'")),
+                }
+            }
+            "--bag" => {
+                self.bag = parse_cubes(option_value(args, arg)?)
+                    .map_err(|e| format!("invalid bag: {e}"))?
+            }
+            "--game" => {
+                let id = option_value(args, arg)?;
+                self.game = Some(
+                    id.parse()
+                        .map_err(|e| format!("invalid game ID '{id}': {e}"))?,
+                );
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn run(&mut self, input: &Path, name: &str) -> Result<(), String> {
+        let content = read_input(input)?;
+        let games = Puzzle::parse(&content).map_err(|e| e.to_string())?;
+        println!("Day 2, {name}, bag: {}", self.bag);
+        self.answer(&games)
+    }
+}
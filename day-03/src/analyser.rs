@@ -0,0 +1,264 @@
+use crate::{number_symbol_adjacent, parse, SchematicNumber, Symbol};
+
+/// A set of characters of the schematic.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum CharClass {
+    /// Every character that is neither a digit nor a `.`.
+    Any,
+    Only(Vec<u8>),
+    /// Every character that is neither a digit nor a `.` nor one of these.
+    Except(Vec<u8>),
+}
+
+impl CharClass {
+    pub fn contains(&self, c: u8) -> bool {
+        if c.is_ascii_digit() || c == b'.' || c == b'\n' || c == b'\r' {
+            return false;
+        }
+        match self {
+            CharClass::Any => true,
+            CharClass::Only(chars) => chars.contains(&c),
+            CharClass::Except(chars) => !chars.contains(&c),
+        }
+    }
+}
+
+/// When a symbol counts as adjacent to a number.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Adjacency {
+    /// Directly left, right, above or below a digit of the number.
+    Orthogonal,
+    /// Next to a digit of the number, including diagonally (the rule of the puzzle).
+    Diagonal,
+    /// At most this many lines and columns away from a digit of the number.
+    Distance(usize),
+}
+
+impl Adjacency {
+    pub fn adjacent(self, number: &SchematicNumber, symbol: &Symbol) -> bool {
+        let last = number.first + number.length - 1;
+        let dy = (symbol.line - number.line).unsigned_abs();
+        let dx = if symbol.col < number.first {
+            (number.first - symbol.col).unsigned_abs()
+        } else if symbol.col > last {
+            (symbol.col - last).unsigned_abs()
+        } else {
+            0
+        };
+        match self {
+            Adjacency::Orthogonal => dx + dy == 1,
+            Adjacency::Diagonal => number_symbol_adjacent(number, symbol),
+            Adjacency::Distance(distance) => dx.max(dy) <= distance,
+        }
+    }
+}
+
+/// Finds part numbers and gears with configurable rules. The default rules are those of the
+/// puzzle: any character but digits and `.` is a symbol, adjacency includes diagonals and a gear is
+/// a `*` next to exactly two numbers.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Analyser {
+    symbols: CharClass,
+    gears: CharClass,
+    adjacency: Adjacency,
+    gear_arity: usize,
+}
+
+impl Default for Analyser {
+    fn default() -> Self {
+        Analyser {
+            symbols: CharClass::Any,
+            gears: CharClass::Only(vec![b'*']),
+            adjacency: Adjacency::Diagonal,
+            gear_arity: 2,
+        }
+    }
+}
+
+impl Analyser {
+    /// Which characters are symbols. Other characters are ignored like a `.`.
+    pub fn with_symbols(mut self, symbols: CharClass) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
+    /// Which symbols can be gears.
+    pub fn with_gears(mut self, gears: CharClass) -> Self {
+        self.gears = gears;
+        self
+    }
+
+    pub fn with_adjacency(mut self, adjacency: Adjacency) -> Self {
+        self.adjacency = adjacency;
+        self
+    }
+
+    /// The number of adjacent numbers a gear needs to have.
+    pub fn with_gear_arity(mut self, gear_arity: usize) -> Self {
+        self.gear_arity = gear_arity;
+        self
+    }
+
+    /// Finds the numbers and symbols in a rectangular schematic and which of them are adjacent.
+    pub fn analyse(&self, schematic: &[u8]) -> Analysis {
+        let (numbers, symbols) = parse(schematic);
+        let symbols: Vec<Symbol> = symbols
+            .into_iter()
+            .filter(|symbol| self.symbols.contains(symbol.s))
+            .collect();
+        let mut symbols_of: Vec<Vec<usize>> = vec![Vec::new(); numbers.len()];
+        let mut numbers_of: Vec<Vec<usize>> = vec![Vec::new(); symbols.len()];
+        for (n, number) in numbers.iter().enumerate() {
+            for (s, symbol) in symbols.iter().enumerate() {
+                if self.adjacency.adjacent(number, symbol) {
+                    symbols_of[n].push(s);
+                    numbers_of[s].push(n);
+                }
+            }
+        }
+        let gears = numbers_of
+            .iter()
+            .enumerate()
+            .filter(|(s, adjacent)| {
+                self.gears.contains(symbols[*s].s) && adjacent.len() == self.gear_arity
+            })
+            .map(|(s, _)| s)
+            .collect();
+        Analysis {
+            numbers,
+            symbols,
+            symbols_of,
+            numbers_of,
+            gears,
+        }
+    }
+}
+
+/// The numbers and symbols of a schematic and the graph of which of them are adjacent. Numbers and
+/// symbols are referred to by their index, in the order they appear in the schematic.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Analysis {
+    pub numbers: Vec<SchematicNumber>,
+    pub symbols: Vec<Symbol>,
+    // the indices of the adjacent symbols of each number
+    symbols_of: Vec<Vec<usize>>,
+    // the indices of the adjacent numbers of each symbol
+    numbers_of: Vec<Vec<usize>>,
+    gears: Vec<usize>,
+}
+
+impl Analysis {
+    /// The indices of the symbols next to the number with the given index.
+    pub fn adjacent_symbols(&self, number: usize) -> &[usize] {
+        &self.symbols_of[number]
+    }
+
+    /// The indices of the numbers next to the symbol with the given index.
+    pub fn adjacent_numbers(&self, symbol: usize) -> &[usize] {
+        &self.numbers_of[symbol]
+    }
+
+    /// All edges of the adjacency graph as pairs of number and symbol index.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.symbols_of
+            .iter()
+            .enumerate()
+            .flat_map(|(n, symbols)| symbols.iter().map(move |s| (n, *s)))
+    }
+
+    /// Whether the number with the given index is next to any symbol.
+    pub fn is_part_number(&self, number: usize) -> bool {
+        !self.symbols_of[number].is_empty()
+    }
+
+    /// The indices of the symbols that are gears.
+    pub fn gears(&self) -> &[usize] {
+        &self.gears
+    }
+
+    pub fn part_numbers(&self) -> impl Iterator<Item = &SchematicNumber> {
+        self.numbers
+            .iter()
+            .enumerate()
+            .filter(|(n, _)| self.is_part_number(*n))
+            .map(|(_, number)| number)
+    }
+
+    pub fn part_numbers_sum(&self) -> u64 {
+        self.part_numbers().map(|number| number.value as u64).sum()
+    }
+
+    /// The product of the numbers next to the gear with the given symbol index.
+    pub fn gear_ratio(&self, gear: usize) -> u64 {
+        self.numbers_of[gear]
+            .iter()
+            .map(|n| self.numbers[*n].value as u64)
+            .product()
+    }
+
+    pub fn gear_ratio_sum(&self) -> u64 {
+        self.gears.iter().map(|gear| self.gear_ratio(*gear)).sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SCHEMATIC: &[u8] = br#"467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+"#;
+
+    #[test]
+    fn default_analyser_works_for_example() {
+        // when
+        let analysis = Analyser::default().analyse(SCHEMATIC);
+
+        // then
+        assert_eq!(analysis.part_numbers_sum(), 4361);
+        assert_eq!(analysis.gear_ratio_sum(), 467835);
+        assert_eq!(analysis.edges().count(), 8);
+        let gears: Vec<(isize, isize)> = analysis
+            .gears()
+            .iter()
+            .map(|s| (analysis.symbols[*s].line, analysis.symbols[*s].col))
+            .collect();
+        assert_eq!(gears, vec![(1, 3), (8, 5)]);
+    }
+
+    #[test]
+    fn analyser_works_with_other_rules() {
+        // given
+        let orthogonal = Analyser::default().with_adjacency(Adjacency::Orthogonal);
+        let only_stars = Analyser::default().with_symbols(CharClass::Only(vec![b'*']));
+        let far_gears = Analyser::default()
+            .with_adjacency(Adjacency::Distance(2))
+            .with_gears(CharClass::Except(vec![b'$']))
+            .with_gear_arity(3);
+
+        // when
+        let orthogonal = orthogonal.analyse(SCHEMATIC);
+        let only_stars = only_stars.analyse(SCHEMATIC);
+        let far_gears = far_gears.analyse(SCHEMATIC);
+
+        // then
+        assert_eq!(orthogonal.part_numbers_sum(), 35 + 633 + 617 + 664 + 598);
+        assert_eq!(orthogonal.gear_ratio_sum(), 0);
+        assert_eq!(only_stars.part_numbers_sum(), 467 + 35 + 617 + 755 + 598);
+        assert_eq!(only_stars.symbols.len(), 3);
+        // the '*' in line 9 is next to four numbers, the '#' is next to only two
+        assert_eq!(far_gears.gears().len(), 3);
+        assert_eq!(
+            far_gears.gear_ratio_sum(),
+            467 * 35 * 114 + 35 * 617 * 592 + 592 * 58 * 755
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//! Day 3: Gear Ratios
+//!
+//! Part numbers and gears in an engine schematic.
+
+use common::{Answer, ErrorKind, ParseError, Solution};
+
+mod analyser;
+mod mode;
+mod render;
+pub use analyser::{Adjacency, Analyser, Analysis, CharClass};
+pub use mode::RenderMode;
+pub use render::{markings, render_ansi, render_html, Marking};
+
+/// Solves both parts of the puzzle, see [`Solution`].
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 3;
+
+    type Input<'a> = (Vec<SchematicNumber>, Vec<Symbol>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        check_rectangular(input)?;
+        Ok(parse(input.as_bytes()))
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Answer {
+        let (numbers, symbols) = input;
+        part_numbers_sum(numbers, symbols).into()
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Answer {
+        let (numbers, symbols) = input;
+        gear_ratio_sum(numbers, symbols).into()
+    }
+}
+
+/// A number in the schematic, which starts in column `first` of `line` and has `length` digits.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SchematicNumber {
+    pub value: u32,
+    pub line: isize,
+    pub first: isize,
+    pub length: isize,
+}
+
+/// Any character in the schematic that is neither a digit nor a `.`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Symbol {
+    pub s: u8,
+    pub line: isize,
+    pub col: isize,
+}
+
+fn check_rectangular(input: &str) -> Result<(), ParseError> {
+    let mut lines = input.lines().enumerate();
+    let width = lines.next().map(|(_, line)| line.len()).unwrap_or(0);
+    match lines.find(|(_, line)| line.len() != width) {
+        Some((y, line)) => {
+            let kind = ErrorKind::Invalid(format!(
+                "expected {width} characters like in the first line, found {}",
+                line.len()
+            ));
+            Err(ParseError::at(line, line, kind).on_line(y + 1))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Finds all numbers and symbols in a schematic, which is expected to be rectangular.
+pub fn parse(schematic: &[u8]) -> (Vec<SchematicNumber>, Vec<Symbol>) {
+    // assume input is rectangular
+    let width = schematic
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| **c == b'\n')
+        .map(|(i, _)| i + 1)
+        .next()
+        .unwrap_or(schematic.len());
+    let height = schematic.len() / width;
+
+    let mut numbers: Vec<SchematicNumber> = Vec::with_capacity(width * height / 3);
+    let mut symbols: Vec<Symbol> = Vec::with_capacity(width * height);
+    let mut current: Option<(u32, usize)> = None;
+
+    for y in 0..height {
+        for x in 0..width {
+            let c = schematic[y * width + x];
+            if c.is_ascii_digit() {
+                let digit = (c - b'0') as u32;
+                if let Some((number, start)) = current {
+                    current = Some((number * 10 + digit, start));
+                } else {
+                    current = Some((digit, x));
+                }
+            } else {
+                if let Some((number, start)) = current {
+                    numbers.push(SchematicNumber {
+                        value: number,
+                        line: y as isize,
+                        first: start as isize,
+                        length: (x - start) as isize,
+                    });
+                    current = None;
+                }
+                // line breaks may be "\r\n"
+                if c != b'.' && c != b'\n' && c != b'\r' {
+                    symbols.push(Symbol {
+                        s: c,
+                        line: y as isize,
+                        col: x as isize,
+                    })
+                }
+            }
+        }
+    }
+    (numbers, symbols)
+}
+
+/// Sums up all numbers that are adjacent to a symbol, including diagonally.
+pub fn part_numbers_sum(numbers: &[SchematicNumber], symbols: &[Symbol]) -> u32 {
+    numbers
+        .iter()
+        .filter(|number| {
+            symbols
+                .iter()
+                .any(|symbol| number_symbol_adjacent(number, symbol))
+        })
+        .map(|number| number.value)
+        .sum::<u32>()
+}
+
+/// Whether the symbol is next to any digit of the number, including diagonally.
+pub fn number_symbol_adjacent(number: &SchematicNumber, symbol: &Symbol) -> bool {
+    (symbol.line == number.line
+        && (symbol.col == number.first - 1 || symbol.col == number.first + number.length))
+        || ((symbol.line == number.line - 1 || symbol.line == number.line + 1)
+            && symbol.col >= number.first - 1
+            && symbol.col <= number.first + number.length)
+}
+
+/// Sums up the gear ratios, the product of the two numbers adjacent to a `*`, if there are exactly
+/// two of them. See [`Analyser`] for other rules.
+pub fn gear_ratio_sum(numbers: &[SchematicNumber], symbols: &[Symbol]) -> u32 {
+    symbols
+        .iter()
+        .filter(|symbol| symbol.s == b'*')
+        .filter_map(|symbol| {
+            let (n, gear_ratio) = numbers
+                .iter()
+                .filter(|number| number_symbol_adjacent(number, symbol))
+                .fold((0usize, 1u32), |(n, gear_ratio), number| {
+                    (n + 1, number.value * gear_ratio)
+                });
+            if n == 2 {
+                Some(gear_ratio)
+            } else {
+                None
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SCHEMATIC: &[u8] = br#"467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+"#;
+
+    #[test]
+    fn part_number_sum_works_for_example() {
+        // given
+        let (numbers, symbols) = parse(SCHEMATIC);
+
+        // when
+        let result = part_numbers_sum(&numbers, &symbols);
+
+        // then
+        assert_eq!(result, 4361);
+    }
+
+    #[test]
+    fn gear_ratio_sum_works_for_example() {
+        // given
+        let (numbers, symbols) = parse(SCHEMATIC);
+
+        // when
+        let result = gear_ratio_sum(&numbers, &symbols);
+
+        // then
+        assert_eq!(result, 467835);
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{markings, render_ansi, render_html, Marking, Puzzle};
+use common::{option_value, read_input, Mode, Solution};
+use std::path::Path;
+
+/// The `--render` mode of the binary: prints the schematic with the part numbers, ignored numbers,
+/// gears and other symbols marked, e.g. to find out why an answer is wrong.
+#[derive(Default)]
+pub struct RenderMode {
+    html: bool,
+}
+
+impl Mode for RenderMode {
+    fn flags(&self) -> &[&str] {
+        &["--render"]
+    }
+
+    fn usage(&self) -> &str {
+        "--render ansi|html"
+    }
+
+    fn argument(
+        &mut self,
+        arg: &str,
+        args: &mut dyn Iterator<Item = &String>,
+    ) -> Result<bool, String> {
+        match arg {
+            "--render" => {
+                self.html = match option_value(args, arg)? {
+                    "ansi" => false,
+                    "html" => true,
+                    other => return Err(format!("unknown output '{other}'")),
+                }
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn run(&mut self, input: &Path, name: &str) -> Result<(), String> {
+        let content = read_input(input)?;
+        let (numbers, symbols) = Puzzle::parse(&content).map_err(|e| e.to_string())?;
+        let markings = markings(&content, &numbers, &symbols);
+        if self.html {
+            print!("{}", render_html(&content, &markings));
+        } else {
+            print!("{}", render_ansi(&content, &markings));
+        }
+        // every number is counted by its first digit
+        let ignored = numbers
+            .iter()
+            .filter(|n| markings[n.line as usize][n.first as usize] == Marking::IgnoredNumber)
+            .count();
+        let gears = markings
+            .iter()
+            .flatten()
+            .filter(|m| **m == Marking::Gear)
+            .count();
+        eprintln!(
+            "{name}: {} part numbers, {ignored} ignored numbers, {gears} gears",
+            numbers.len() - ignored
+        );
+        Ok(())
+    }
+}
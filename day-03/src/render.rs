@@ -0,0 +1,233 @@
+use crate::{number_symbol_adjacent, SchematicNumber, Symbol};
+use std::fmt::Write;
+
+/// What a character of the schematic was counted as.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Marking {
+    /// A digit of a number next to a symbol, which counts for part one.
+    PartNumber,
+    /// A digit of a number that is not next to any symbol.
+    IgnoredNumber,
+    /// A `*` next to exactly two numbers, which counts for part two.
+    Gear,
+    /// Any other symbol.
+    Symbol,
+    /// A `.`.
+    Blank,
+}
+
+impl Marking {
+    fn ansi(self) -> &'static str {
+        match self {
+            Marking::PartNumber => "\x1b[1;32m",
+            Marking::IgnoredNumber => "\x1b[31m",
+            Marking::Gear => "\x1b[1;33m",
+            Marking::Symbol => "\x1b[36m",
+            Marking::Blank => "\x1b[2m",
+        }
+    }
+
+    fn css_class(self) -> &'static str {
+        match self {
+            Marking::PartNumber => "part-number",
+            Marking::IgnoredNumber => "ignored-number",
+            Marking::Gear => "gear",
+            Marking::Symbol => "symbol",
+            Marking::Blank => "blank",
+        }
+    }
+}
+
+/// The marking of every character of every line of the schematic, with the same rules as
+/// [`crate::part_numbers_sum`] and [`crate::gear_ratio_sum`].
+pub fn markings(
+    schematic: &str,
+    numbers: &[SchematicNumber],
+    symbols: &[Symbol],
+) -> Vec<Vec<Marking>> {
+    let mut markings: Vec<Vec<Marking>> = schematic
+        .lines()
+        .map(|line| vec![Marking::Blank; line.len()])
+        .collect();
+    for number in numbers {
+        let marking = if symbols
+            .iter()
+            .any(|symbol| number_symbol_adjacent(number, symbol))
+        {
+            Marking::PartNumber
+        } else {
+            Marking::IgnoredNumber
+        };
+        let first = number.first as usize;
+        markings[number.line as usize][first..first + number.length as usize].fill(marking);
+    }
+    for symbol in symbols {
+        let adjacent = numbers
+            .iter()
+            .filter(|number| number_symbol_adjacent(number, symbol))
+            .count();
+        markings[symbol.line as usize][symbol.col as usize] = if symbol.s == b'*' && adjacent == 2 {
+            Marking::Gear
+        } else {
+            Marking::Symbol
+        };
+    }
+    markings
+}
+
+// consecutive characters of a line with the same marking
+fn spans<'a>(
+    line: &'a str,
+    markings: &'a [Marking],
+) -> impl Iterator<Item = (Marking, &'a str)> + 'a {
+    let mut start = 0;
+    (1..=line.len()).filter_map(move |end| {
+        if end == line.len() || markings[end] != markings[start] {
+            let span = (markings[start], &line[start..end]);
+            start = end;
+            Some(span)
+        } else {
+            None
+        }
+    })
+}
+
+/// The schematic with ANSI colors: part numbers green, ignored numbers red, gears yellow and other
+/// symbols cyan.
+pub fn render_ansi(schematic: &str, markings: &[Vec<Marking>]) -> String {
+    let mut out = String::with_capacity(schematic.len() * 4);
+    for (line, markings) in schematic.lines().zip(markings) {
+        for (marking, text) in spans(line, markings) {
+            let _ = write!(out, "{}{text}\x1b[0m", marking.ansi());
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// The schematic as an HTML page, with the same colors as [`render_ansi`].
+pub fn render_html(schematic: &str, markings: &[Vec<Marking>]) -> String {
+    let mut out = String::with_capacity(schematic.len() * 8);
+    out.push_str(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Engine schematic</title>
+<style>
+.part-number { color: green; font-weight: bold; }
+.ignored-number { color: red; }
+.gear { color: orange; font-weight: bold; }
+.symbol { color: teal; }
+.blank { color: silver; }
+</style>
+</head>
+<body>
+<pre>
+"#,
+    );
+    for (line, markings) in schematic.lines().zip(markings) {
+        for (marking, text) in spans(line, markings) {
+            let _ = write!(out, r#"<span class="{}">"#, marking.css_class());
+            for c in text.chars() {
+                match c {
+                    '<' => out.push_str("&lt;"),
+                    '>' => out.push_str("&gt;"),
+                    '&' => out.push_str("&amp;"),
+                    c => out.push(c),
+                }
+            }
+            out.push_str("</span>");
+        }
+        out.push('\n');
+    }
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse;
+
+    const SCHEMATIC: &str = r#"467..114..
+...*......
+..35..633.
+"#;
+
+    #[test]
+    fn markings_work_for_example() {
+        // given
+        let (numbers, symbols) = parse(SCHEMATIC.as_bytes());
+
+        // when
+        let markings = markings(SCHEMATIC, &numbers, &symbols);
+
+        // then
+        use Marking::*;
+        assert_eq!(
+            markings[0][..6],
+            [
+                PartNumber,
+                PartNumber,
+                PartNumber,
+                Blank,
+                Blank,
+                IgnoredNumber
+            ]
+        );
+        assert_eq!(markings[1][3], Gear);
+        assert_eq!(markings[2][2..4], [PartNumber, PartNumber]);
+        assert_eq!(markings[2][6], IgnoredNumber);
+    }
+
+    #[test]
+    fn markings_work_with_crlf_line_breaks() {
+        // given
+        let schematic = SCHEMATIC.replace('\n', "\r\n");
+        let (numbers, symbols) = parse(schematic.as_bytes());
+
+        // when
+        let crlf = markings(&schematic, &numbers, &symbols);
+
+        // then
+        assert_eq!(symbols.len(), 1);
+        let (numbers, symbols) = parse(SCHEMATIC.as_bytes());
+        assert_eq!(crlf, markings(SCHEMATIC, &numbers, &symbols));
+    }
+
+    #[test]
+    fn render_ansi_works() {
+        // given
+        let schematic = "1.\n.#\n";
+        let (numbers, symbols) = parse(schematic.as_bytes());
+        let markings = markings(schematic, &numbers, &symbols);
+
+        // when
+        let rendered = render_ansi(schematic, &markings);
+
+        // then
+        assert_eq!(
+            rendered,
+            "\x1b[1;32m1\x1b[0m\x1b[2m.\x1b[0m\n\x1b[2m.\x1b[0m\x1b[36m#\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn render_html_escapes_symbols() {
+        // given
+        let schematic = "12&\n<..\n";
+        let (numbers, symbols) = parse(schematic.as_bytes());
+        let markings = markings(schematic, &numbers, &symbols);
+
+        // when
+        let rendered = render_html(schematic, &markings);
+
+        // then
+        assert!(rendered
+            .contains(r#"<span class="part-number">12</span><span class="symbol">&amp;</span>"#));
+        assert!(
+            rendered.contains(r#"<span class="symbol">&lt;</span><span class="blank">..</span>"#)
+        );
+    }
+}
@@ -0,0 +1,307 @@
+use crate::Card;
+use std::fmt::Write;
+
+/// What happens to copies won of cards past the end of the table.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Overflow {
+    /// They are lost (the rule of the puzzle).
+    Drop,
+    /// They continue at the first card. Copies won of cards that have already been scratched are
+    /// kept, but do not win anything, so the cascade still ends.
+    Wrap,
+}
+
+/// How many points a card with matching numbers is worth.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Points {
+    /// One point for the first match, multiplied by `factor` for each further one (the rule of the
+    /// puzzle has a factor of 2).
+    Multiply { factor: u64 },
+    /// One point for each match.
+    Linear,
+}
+
+/// The rules of a scratchcard game. The default rules are those of the puzzle.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Rules {
+    overflow: Overflow,
+    points: Points,
+    // the maximum number of instances of a single card
+    cap: Option<u64>,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            overflow: Overflow::Drop,
+            points: Points::Multiply { factor: 2 },
+            cap: None,
+        }
+    }
+}
+
+impl Rules {
+    pub fn with_overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    pub fn with_points(mut self, points: Points) -> Self {
+        self.points = points;
+        self
+    }
+
+    /// Limits the instances of each card, further copies are lost.
+    pub fn with_cap(mut self, cap: u64) -> Self {
+        self.cap = Some(cap);
+        self
+    }
+
+    /// The points of a card with the given number of matching numbers, `None` if they do not fit
+    /// into a `u64`.
+    pub fn points(&self, matches: usize) -> Option<u64> {
+        match (matches, self.points) {
+            (0, _) => Some(0),
+            (matches, Points::Multiply { factor }) => {
+                factor.checked_pow(u32::try_from(matches - 1).ok()?)
+            }
+            (matches, Points::Linear) => u64::try_from(matches).ok(),
+        }
+    }
+}
+
+/// Sums up the points of all cards with the given rules, fails if there are too many of them.
+pub fn total_points_with(cards: &[Card], rules: &Rules) -> Result<u64, String> {
+    cards.iter().enumerate().try_fold(0u64, |sum, (i, card)| {
+        rules
+            .points(card.matches())
+            .and_then(|points| sum.checked_add(points))
+            .ok_or_else(|| format!("too many points with card {}", i + 1))
+    })
+}
+
+/// What happened when all instances of a card were scratched.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Step {
+    /// The index of the scratched card.
+    pub card: usize,
+    /// The instances of the scratched card, each of them won a copy of each of the `won` cards.
+    pub instances: u64,
+    /// The indices of the cards copies were won of.
+    pub won: Vec<usize>,
+    /// The instances of every card after this step.
+    pub counts: Vec<u64>,
+}
+
+/// Scratches the cards one after another, winning copies of the following cards.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Cascade {
+    rules: Rules,
+    matches: Vec<usize>,
+    counts: Vec<u64>,
+    // the index of the next card to scratch
+    next: usize,
+}
+
+impl Cascade {
+    /// Starts with one instance of every card.
+    pub fn new(cards: &[Card], rules: Rules) -> Self {
+        let initial = rules.cap.unwrap_or(u64::MAX).min(1);
+        Cascade {
+            rules,
+            matches: cards.iter().map(Card::matches).collect(),
+            counts: vec![initial; cards.len()],
+            next: 0,
+        }
+    }
+
+    /// The instances of every card so far.
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// The instances of all cards so far, at most `u64::MAX`.
+    pub fn total(&self) -> u64 {
+        self.counts
+            .iter()
+            .fold(0, |sum, count| sum.saturating_add(*count))
+    }
+
+    /// Scratches all instances of the next card, `None` if all cards have been scratched.
+    pub fn step(&mut self) -> Option<Step> {
+        let card = self.next;
+        let matches = *self.matches.get(card)?;
+        self.next += 1;
+
+        let n = self.counts.len();
+        let won: Vec<usize> = match self.rules.overflow {
+            Overflow::Drop => ((card + 1)..n.min(card + matches + 1)).collect(),
+            Overflow::Wrap => (1..=matches.min(n - 1)).map(|k| (card + k) % n).collect(),
+        };
+        let instances = self.counts[card];
+        let cap = self.rules.cap.unwrap_or(u64::MAX);
+        for j in &won {
+            self.counts[*j] = self.counts[*j].saturating_add(instances).min(cap);
+        }
+        Some(Step {
+            card,
+            instances,
+            won,
+            counts: self.counts.clone(),
+        })
+    }
+
+    /// Scratches all remaining cards.
+    pub fn run(mut self) -> Self {
+        while self.step().is_some() {}
+        self
+    }
+}
+
+/// The instances of every card (columns) initially and after each step (rows), as CSV. Step `k`
+/// is the scratching of card `k`.
+pub fn cascade_table(cards: &[Card], rules: Rules) -> String {
+    let mut cascade = Cascade::new(cards, rules);
+    let mut table = String::with_capacity((cards.len() + 1) * cards.len() * 4);
+    table.push_str("step");
+    for i in 1..=cards.len() {
+        let _ = write!(table, ",card {i}");
+    }
+    table.push_str(",total\n");
+    table_row(&mut table, 0, cascade.counts());
+    while let Some(step) = cascade.step() {
+        table_row(&mut table, step.card + 1, &step.counts);
+    }
+    table
+}
+
+fn table_row(table: &mut String, step: usize, counts: &[u64]) {
+    let _ = write!(table, "{step}");
+    for count in counts {
+        let _ = write!(table, ",{count}");
+    }
+    let total = counts
+        .iter()
+        .fold(0u64, |sum, count| sum.saturating_add(*count));
+    let _ = writeln!(table, ",{total}");
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{parse, total_points, total_scratchcards};
+
+    const EXAMPLE: &str = r#"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+"#;
+
+    #[test]
+    fn default_rules_work_like_puzzle() {
+        // given
+        let cards = parse(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let cascade = Cascade::new(&cards, Rules::default()).run();
+
+        // then
+        assert_eq!(cascade.counts(), &[1, 2, 4, 8, 14, 1]);
+        assert_eq!(cascade.total() as usize, total_scratchcards(&cards));
+        assert_eq!(
+            total_points_with(&cards, &Rules::default()),
+            Ok(total_points(&cards) as u64)
+        );
+    }
+
+    #[test]
+    fn steps_report_copies() {
+        // given
+        let cards = parse(EXAMPLE).expect("expected successful parsing");
+        let mut cascade = Cascade::new(&cards, Rules::default());
+
+        // when
+        let first = cascade.step();
+        let second = cascade.step();
+
+        // then
+        assert_eq!(
+            first,
+            Some(Step {
+                card: 0,
+                instances: 1,
+                won: vec![1, 2, 3, 4],
+                counts: vec![1, 2, 2, 2, 2, 1]
+            })
+        );
+        assert_eq!(second.map(|step| step.counts), Some(vec![1, 2, 4, 4, 2, 1]));
+    }
+
+    #[test]
+    fn alternative_rules_work() {
+        // given
+        let cards = parse(EXAMPLE).expect("expected successful parsing");
+        // the first card with its four matches is last, so its copies wrap around
+        let mut wrapping = cards.clone();
+        wrapping.swap(0, 5);
+
+        // when
+        let wrapped = Cascade::new(&wrapping, Rules::default().with_overflow(Overflow::Wrap)).run();
+        let capped = Cascade::new(&cards, Rules::default().with_cap(3)).run();
+        let linear = total_points_with(&cards, &Rules::default().with_points(Points::Linear));
+        let tripled = total_points_with(
+            &cards,
+            &Rules::default().with_points(Points::Multiply { factor: 3 }),
+        );
+
+        // then
+        assert_eq!(wrapped.counts(), &[2, 2, 3, 5, 7, 1]);
+        assert_eq!(capped.counts(), &[1, 2, 3, 3, 3, 1]);
+        assert_eq!(linear, Ok(4 + 2 + 2 + 1));
+        assert_eq!(tripled, Ok(27 + 3 + 3 + 1));
+    }
+
+    #[test]
+    fn total_points_with_fails_for_too_many_points() {
+        // given
+        let winning: Vec<String> = (1..=21).map(|n| n.to_string()).collect();
+        let card = format!(
+            "Card 1: {} | {}\nCard 2: 1 | 1\n",
+            winning.join(" "),
+            winning.join(" ")
+        );
+        let cards = parse(&card).expect("expected successful parsing");
+        let rules = Rules::default().with_points(Points::Multiply { factor: 10 });
+
+        // when
+        let points = total_points_with(&cards, &rules);
+
+        // then
+        assert_eq!(rules.points(21), None);
+        assert_eq!(points, Err("too many points with card 1".to_owned()));
+        assert_eq!(rules.points(20), Some(10u64.pow(19)));
+    }
+
+    #[test]
+    fn cascade_table_works() {
+        // given
+        let cards = parse("Card 1: 1 2 | 1 2\nCard 2: 3 | 4\nCard 3: 5 | 5\n")
+            .expect("expected successful parsing");
+
+        // when
+        let table = cascade_table(&cards, Rules::default());
+
+        // then
+        assert_eq!(
+            table,
+            "step,card 1,card 2,card 3,total
+0,1,1,1,3
+1,1,2,2,5
+2,1,2,2,5
+3,1,2,2,5
+"
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! Day 4: Scratchcards
+//!
+//! Scratchcards that win points, or more scratchcards.
+
+use common::{parse_lines, parse_number, split_once, Answer, ParseError, Solution};
+use std::collections::HashSet;
+
+mod cascade;
+mod mode;
+pub use cascade::{cascade_table, total_points_with, Cascade, Overflow, Points, Rules, Step};
+pub use mode::CascadeMode;
+
+/// Solves both parts of the puzzle, see [`Solution`].
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 4;
+
+    type Input<'a> = Vec<Card>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Answer {
+        total_points(input).into()
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Answer {
+        total_scratchcards(input).into()
+    }
+}
+
+/// The winning numbers of a card and the numbers given on it.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Card {
+    pub winning: HashSet<u32>,
+    pub given: HashSet<u32>,
+}
+
+impl Card {
+    /// The number of given numbers that are winning numbers.
+    pub fn matches(&self) -> usize {
+        self.winning.intersection(&self.given).count()
+    }
+}
+
+/// Parses a card from a line like `Card 1: 41 48 83 | 83 86  6 31 17`.
+pub fn parse_card(line: &str) -> Result<Card, ParseError> {
+    let (_, numbers) = split_once(line, line, ": ", "separator between card and numbers")?;
+    let (winning, given) = split_once(
+        line,
+        numbers,
+        " | ",
+        "separator between winning and given numbers",
+    )?;
+
+    let winning: HashSet<u32> = winning
+        .split_whitespace()
+        .map(|s| parse_number(line, s))
+        .collect::<Result<HashSet<u32>, ParseError>>()?;
+    let given: HashSet<u32> = given
+        .split_whitespace()
+        .map(|s| parse_number(line, s))
+        .collect::<Result<HashSet<u32>, ParseError>>()?;
+
+    Ok(Card { winning, given })
+}
+
+/// Parses one card per line.
+pub fn parse(input: &str) -> Result<Vec<Card>, ParseError> {
+    parse_lines(input, parse_card)
+}
+
+/// Sums up the points of all cards: one point for the first matching number, doubled for each
+/// further one.
+pub fn total_points(cards: &[Card]) -> u32 {
+    cards
+        .iter()
+        .map(|card| card.matches() as u32)
+        .filter(|matches| *matches > 0)
+        .map(|matches| 2u32.pow(matches - 1))
+        .sum()
+}
+
+/// Counts the scratchcards you end up with if each card wins copies of the next cards, one for each
+/// matching number. See [`Cascade`] for the single steps and other rules.
+pub fn total_scratchcards(cards: &[Card]) -> usize {
+    let mut card_numbers: Vec<usize> = vec![1; cards.len()];
+
+    for (i, matches) in cards.iter().map(Card::matches).enumerate() {
+        for j in (i + 1)..card_numbers.len().min(i + matches + 1) {
+            card_numbers[j] += card_numbers[i];
+        }
+    }
+
+    card_numbers.iter().sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = r#"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+"#;
+
+    #[test]
+    fn total_points_works_for_example() {
+        // given
+        let cards = parse(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let points = total_points(&cards);
+
+        // then
+        assert_eq!(points, 13);
+    }
+
+    #[test]
+    fn total_scratchcards_works_for_example() {
+        // given
+        let cards = parse(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let number = total_scratchcards(&cards);
+
+        // then
+        assert_eq!(number, 30);
+    }
+
+    #[test]
+    fn parse_card_reports_position_of_invalid_number() {
+        // when
+        let error = parse_card("Card 1: 41 48 | 83 8x 6").expect_err("expected parsing to fail");
+
+        // then
+        assert_eq!(error.column, 20);
+        assert_eq!(error.text, "8x");
+    }
+}
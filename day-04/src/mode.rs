@@ -0,0 +1,63 @@
+use crate::{cascade_table, total_points_with, Cascade, Overflow, Points, Puzzle, Rules};
+use common::{option_value, read_input, Mode, Solution};
+use std::path::Path;
+
+/// The `--cascade` mode of the binary: prints the instances of every card after each step as CSV,
+/// and the totals to stderr.
+#[derive(Default)]
+pub struct CascadeMode {
+    rules: Rules,
+}
+
+impl Mode for CascadeMode {
+    fn flags(&self) -> &[&str] {
+        &["--cascade"]
+    }
+
+    fn usage(&self) -> &str {
+        "--cascade [--wrap] [--cap <copies>] [--points linear|<factor>]"
+    }
+
+    fn argument(
+        &mut self,
+        arg: &str,
+        args: &mut dyn Iterator<Item = &String>,
+    ) -> Result<bool, String> {
+        match arg {
+            "--cascade" => {}
+            "--wrap" => self.rules = self.rules.with_overflow(Overflow::Wrap),
+            "--cap" => {
+                let cap = option_value(args, arg)?;
+                self.rules = self.rules.with_cap(
+                    cap.parse()
+                        .map_err(|e| format!("invalid cap '{cap}': {e}"))?,
+                );
+            }
+            "--points" => {
+                let points = match option_value(args, arg)? {
+                    "linear" => Points::Linear,
+                    factor => Points::Multiply {
+                        factor: factor
+                            .parse()
+                            .map_err(|e| format!("invalid factor '{factor}': {e}"))?,
+                    },
+                };
+                self.rules = self.rules.with_points(points);
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn run(&mut self, input: &Path, name: &str) -> Result<(), String> {
+        let content = read_input(input)?;
+        let cards = Puzzle::parse(&content).map_err(|e| e.to_string())?;
+        let points = total_points_with(&cards, &self.rules)?;
+        print!("{}", cascade_table(&cards, self.rules));
+        eprintln!(
+            "{name}: {points} points, {} scratchcards",
+            Cascade::new(&cards, self.rules).run().total()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,291 @@
+use crate::Map;
+
+/// A map of categories that moves each of a number of intervals by an offset, like a map of the
+/// almanac. It is defined for all categories below `u64::MAX`, categories outside of any moved
+/// interval map to themselves.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct IntervalMap {
+    // Start and offset of each piece, sorted by start. The first piece starts at 0, each piece ends
+    // where the next one starts, the last one at `u64::MAX`.
+    pieces: Vec<(u64, i128)>,
+}
+
+impl Default for IntervalMap {
+    fn default() -> Self {
+        IntervalMap::identity()
+    }
+}
+
+impl IntervalMap {
+    /// Maps every category to itself.
+    pub fn identity() -> Self {
+        IntervalMap {
+            pieces: vec![(0, 0)],
+        }
+    }
+
+    /// The map described by the ranges of an almanac map. If ranges overlap, the first one wins.
+    pub fn from_ranges(ranges: &[Map]) -> Self {
+        ranges
+            .iter()
+            .rev()
+            .fold(IntervalMap::identity(), |map, range| {
+                map.with_range(range.source_start, range.dest_start, range.length)
+            })
+    }
+
+    /// This map, except that `length` categories starting at `source_start` map to the categories
+    /// starting at `dest_start`. Only the part of the range whose source and destination are both
+    /// below `u64::MAX` is used.
+    pub fn with_range(mut self, source_start: u64, dest_start: u64, length: u64) -> Self {
+        let length = length.min(u64::MAX - dest_start);
+        let end = source_start.saturating_add(length);
+        if source_start >= end {
+            return self;
+        }
+        let after = self.piece_at(end);
+        let end_offset = self.pieces[after].1;
+        let first = self.piece_at(source_start);
+        let last = self.pieces.partition_point(|(start, _)| *start < end);
+        let mut replacement: Vec<(u64, i128)> = Vec::with_capacity(2);
+        if self.pieces[first].0 < source_start {
+            replacement.push(self.pieces[first]);
+        }
+        replacement.push((source_start, dest_start as i128 - source_start as i128));
+        if end < u64::MAX && self.pieces.get(last).map(|(start, _)| *start) != Some(end) {
+            replacement.push((end, end_offset));
+        }
+        self.pieces.splice(first..last, replacement);
+        self.simplify()
+    }
+
+    // the index of the piece containing the category
+    fn piece_at(&self, category: u64) -> usize {
+        self.pieces.partition_point(|(start, _)| *start <= category) - 1
+    }
+
+    fn end_of(&self, piece: usize) -> u64 {
+        self.pieces
+            .get(piece + 1)
+            .map(|(start, _)| *start)
+            .unwrap_or(u64::MAX)
+    }
+
+    /// The pieces of this map as `(start, end, offset)`, with an exclusive end. Each category in a
+    /// piece is moved by its offset.
+    pub fn pieces(&self) -> impl Iterator<Item = (u64, u64, i128)> + '_ {
+        self.pieces
+            .iter()
+            .enumerate()
+            .map(|(i, (start, offset))| (*start, self.end_of(i), *offset))
+    }
+
+    /// Maps a single category, in `O(log n)` for `n` pieces.
+    pub fn get(&self, category: u64) -> u64 {
+        (category as i128 + self.pieces[self.piece_at(category)].1) as u64
+    }
+
+    /// Maps the range `start..end`, the result may consist of several ranges, ordered by the part of
+    /// the source range they come from.
+    pub fn map_range(&self, start: u64, end: u64) -> Vec<(u64, u64)> {
+        if start >= end {
+            return vec![];
+        }
+        let first = self.piece_at(start);
+        let last = self
+            .pieces
+            .partition_point(|(piece_start, _)| *piece_start < end);
+        (first..last)
+            .map(|i| {
+                let offset = self.pieces[i].1;
+                let from = self.pieces[i].0.max(start) as i128 + offset;
+                let to = self.end_of(i).min(end) as i128 + offset;
+                (from as u64, to as u64)
+            })
+            .collect()
+    }
+
+    /// The smallest category the range `start..end` maps to, `None` for an empty range.
+    pub fn min_in(&self, start: u64, end: u64) -> Option<u64> {
+        self.map_range(start, end)
+            .iter()
+            .map(|(from, _)| *from)
+            .min()
+    }
+
+    /// The map that first applies this map, then the next one.
+    pub fn then(&self, next: &IntervalMap) -> IntervalMap {
+        let mut pieces: Vec<(u64, i128)> =
+            Vec::with_capacity(self.pieces.len() + next.pieces.len());
+        for (start, end, offset) in self.pieces() {
+            let image_start = (start as i128 + offset) as u64;
+            let image_end = (end as i128 + offset) as u64;
+            let first = next.piece_at(image_start);
+            let last = next
+                .pieces
+                .partition_point(|(next_start, _)| *next_start < image_end);
+            for i in first..last {
+                let piece_start = (next.pieces[i].0.max(image_start) as i128 - offset) as u64;
+                pieces.push((piece_start, offset + next.pieces[i].1));
+            }
+        }
+        IntervalMap { pieces }.simplify()
+    }
+
+    /// Merges neighbouring pieces with the same offset, so equal maps have equal pieces.
+    pub fn simplify(mut self) -> Self {
+        self.pieces.dedup_by(|next, previous| next.1 == previous.1);
+        self
+    }
+
+    /// The map back from the destination to the source categories, if every category below
+    /// `u64::MAX` is the destination of exactly one source category.
+    pub fn invert(&self) -> Option<IntervalMap> {
+        let mut images: Vec<(u64, u64, i128)> = self
+            .pieces()
+            .map(|(start, end, offset)| {
+                let image_start = (start as i128 + offset) as u64;
+                let image_end = (end as i128 + offset) as u64;
+                (image_start, image_end, -offset)
+            })
+            .collect();
+        images.sort_unstable();
+        let mut expected_start = 0;
+        for (start, end, _) in &images {
+            if *start != expected_start {
+                return None;
+            }
+            expected_start = *end;
+        }
+        if expected_start != u64::MAX {
+            return None;
+        }
+        Some(
+            IntervalMap {
+                pieces: images
+                    .into_iter()
+                    .map(|(start, _, offset)| (start, offset))
+                    .collect(),
+            }
+            .simplify(),
+        )
+    }
+
+    /// All categories that map to the given one, even if the map can not be inverted as a whole.
+    pub fn preimage(&self, category: u64) -> Vec<u64> {
+        self.pieces()
+            .filter_map(|(start, end, offset)| {
+                let source = category as i128 - offset;
+                (start as i128 <= source && source < end as i128).then_some(source as u64)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn seed_to_soil() -> IntervalMap {
+        IntervalMap::from_ranges(&[
+            Map {
+                source_start: 50,
+                dest_start: 52,
+                length: 48,
+            },
+            Map {
+                source_start: 98,
+                dest_start: 50,
+                length: 2,
+            },
+        ])
+    }
+
+    #[test]
+    fn get_works_like_map_category() {
+        // given
+        let map = seed_to_soil();
+
+        // then
+        let soils: Vec<u64> = [0, 49, 50, 79, 97, 98, 99, 100]
+            .iter()
+            .map(|seed| map.get(*seed))
+            .collect();
+        assert_eq!(soils, vec![0, 49, 52, 81, 99, 50, 51, 100]);
+        assert_eq!(
+            map.pieces().collect::<Vec<_>>(),
+            vec![(0, 50, 0), (50, 98, 2), (98, 100, -48), (100, u64::MAX, 0)]
+        );
+    }
+
+    #[test]
+    fn map_range_works() {
+        // given
+        let map = seed_to_soil();
+
+        // then
+        assert_eq!(map.map_range(40, 99), vec![(40, 50), (52, 100), (50, 51)]);
+        assert_eq!(map.map_range(60, 70), vec![(62, 72)]);
+        assert_eq!(map.map_range(70, 70), vec![]);
+        assert_eq!(map.min_in(40, 99), Some(40));
+        assert_eq!(map.min_in(95, 99), Some(50));
+    }
+
+    #[test]
+    fn composition_and_inversion_work() {
+        // given
+        let map = seed_to_soil();
+        let shift = IntervalMap::identity()
+            .with_range(0, 10, 10)
+            .with_range(10, 0, 10);
+
+        // when
+        let composed = map.then(&shift);
+        let inverse = map.invert().expect("expected a bijective map");
+
+        // then
+        for seed in [0, 5, 15, 49, 50, 97, 98, 99, 1000] {
+            assert_eq!(composed.get(seed), shift.get(map.get(seed)));
+            assert_eq!(inverse.get(map.get(seed)), seed);
+        }
+        assert_eq!(map.then(&inverse), IntervalMap::identity());
+        assert_eq!(shift.then(&shift), IntervalMap::identity());
+    }
+
+    #[test]
+    fn ranges_work_up_to_the_largest_category() {
+        // given
+        let map = IntervalMap::from_ranges(&[Map {
+            source_start: 0,
+            dest_start: u64::MAX - 10,
+            length: 10,
+        }]);
+        let clipped = IntervalMap::identity().with_range(0, u64::MAX - 10, 20);
+
+        // then
+        assert_eq!(
+            (map.get(0), map.get(9), map.get(10)),
+            (u64::MAX - 10, u64::MAX - 1, 10)
+        );
+        assert_eq!(
+            map.map_range(5, 15),
+            vec![(u64::MAX - 5, u64::MAX), (10, 15)]
+        );
+        assert_eq!(clipped, map);
+        let twice = map.then(&map);
+        assert_eq!(twice.pieces().next(), Some((0, 10, u64::MAX as i128 - 10)));
+        assert_eq!(twice.get(u64::MAX - 1), u64::MAX - 1);
+        assert_eq!(map.invert(), None);
+    }
+
+    #[test]
+    fn invert_fails_for_maps_that_are_not_bijective() {
+        // given
+        let merging = IntervalMap::identity().with_range(10, 0, 5);
+
+        // then
+        assert_eq!(merging.invert(), None);
+        assert_eq!(merging.preimage(3), vec![3, 13]);
+        assert_eq!(merging.preimage(12), vec![]);
+    }
+}
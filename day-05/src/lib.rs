@@ -0,0 +1,360 @@
+//! Day 5: If You Give A Seed A Fertilizer
+//!
+//! Seeds that are mapped from category to category until their location is known.
+
+use common::{parse_number, split_once, Answer, ErrorKind, ParseError, Solution};
+
+mod interval_map;
+mod mode;
+mod validate;
+pub use interval_map::IntervalMap;
+pub use mode::ValidateMode;
+pub use validate::{validate, Issue, Problem};
+
+/// Solves both parts of the puzzle, see [`Solution`].
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 5;
+
+    type Input<'a> = Almanac;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Answer {
+        match map_seeds(input).iter().min() {
+            Some(min) => (*min).into(),
+            None => Answer::unsolved("There were no seeds"),
+        }
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Answer {
+        match map_seed_ranges(input).iter().map(|(start, _)| start).min() {
+            Some(min) => (*min).into(),
+            None => Answer::unsolved("There were no seed ranges"),
+        }
+    }
+}
+
+/// The seeds (or seed ranges, for part two) and the maps from one category to the next, in order.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub maps: Vec<Vec<Map>>,
+}
+
+impl Almanac {
+    /// The map of each category to the next, in order.
+    pub fn category_maps(&self) -> Vec<IntervalMap> {
+        self.maps
+            .iter()
+            .map(|ranges| IntervalMap::from_ranges(ranges))
+            .collect()
+    }
+
+    /// All maps composed into one, from seed to location.
+    pub fn seed_to_location(&self) -> IntervalMap {
+        self.category_maps()
+            .iter()
+            .fold(IntervalMap::identity(), |map, next| map.then(next))
+    }
+
+    /// The map back from location to seed, if every location belongs to exactly one seed.
+    pub fn location_to_seed(&self) -> Option<IntervalMap> {
+        self.seed_to_location().invert()
+    }
+
+    /// The seed ranges of part two as `(start, end)` with an exclusive end.
+    pub fn seed_ranges(&self) -> Vec<(u64, u64)> {
+        // assumption: input seeds are valid ranges (i.e. seeds array has an even length)
+        self.seeds
+            .chunks_exact(2)
+            .map(|chunk| (chunk[0], chunk[0] + chunk[1]))
+            .collect()
+    }
+}
+
+/// One range of a map: `length` categories starting at `source_start` map to the categories
+/// starting at `dest_start`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Map {
+    pub source_start: u64,
+    pub dest_start: u64,
+    pub length: u64,
+}
+
+/// Parses the almanac. The ranges of each map are sorted by their source start.
+pub fn parse(input: &str) -> Result<Almanac, ParseError> {
+    let (seeds, maps) = split_once(input, input, "\n\n", "empty line between seeds and maps")?;
+    let seeds = seeds
+        .strip_prefix("seeds: ")
+        .ok_or_else(|| ParseError::at(input, seeds, ErrorKind::Missing("prefix 'seeds: '")))?
+        .split_whitespace()
+        .map(|s| parse_number(input, s))
+        .collect::<Result<Vec<u64>, ParseError>>()?;
+
+    // assumption: the maps are in the correct order and we can ignore the source and destination
+    // category
+    let maps = maps
+        .split("\n\n")
+        .map(|block| parse_map(input, block))
+        .collect::<Result<Vec<Vec<Map>>, ParseError>>()?;
+
+    Ok(Almanac { seeds, maps })
+}
+
+// block is a slice of input, which is only needed to find the position of errors
+fn parse_map(input: &str, block: &str) -> Result<Vec<Map>, ParseError> {
+    let mut map = parse_ranges(input, block)?;
+    // stable, so that of two ranges with the same source start, the first one wins
+    map.sort_by_key(|m| m.source_start);
+    Ok(map)
+}
+
+// the ranges of a map in the order of their lines
+pub(crate) fn parse_ranges(input: &str, block: &str) -> Result<Vec<Map>, ParseError> {
+    // assumption: the map starts correctly with the source and destination category and we can
+    // ignore it
+    block
+        .lines()
+        .skip(1)
+        .map(|line| {
+            let (dest, rest) = split_once(input, line, " ", "separator after destination")?;
+            let dest_start: u64 = parse_number(input, dest)?;
+
+            let (source, length) = split_once(input, rest, " ", "separator after source")?;
+            let source_start: u64 = parse_number(input, source)?;
+            let length: u64 = parse_number(input, length)?;
+            // the interval maps are only defined below u64::MAX
+            if source_start.checked_add(length).is_none()
+                || dest_start.checked_add(length).is_none()
+            {
+                let kind = ErrorKind::Invalid("range ends after the largest category".to_owned());
+                return Err(ParseError::at(input, line, kind));
+            }
+
+            Ok(Map {
+                source_start,
+                dest_start,
+                length,
+            })
+        })
+        .collect()
+}
+
+/// The location of every seed.
+pub fn map_seeds(almanac: &Almanac) -> Vec<u64> {
+    let map = almanac.seed_to_location();
+    almanac.seeds.iter().map(|seed| map.get(*seed)).collect()
+}
+
+/// Maps a single category, categories that are not in any range map to themselves.
+pub fn map_category(cat: u64, map: &[Map]) -> u64 {
+    // assumption: mapping ranges do not overlap
+    map.iter()
+        .filter(|m| m.source_start <= cat && cat < m.source_start + m.length)
+        .map(|m| (cat - m.source_start) + m.dest_start)
+        .next()
+        .unwrap_or(cat)
+}
+
+/// The location ranges of all seed ranges, each given as `(start, end)` with an exclusive end.
+pub fn map_seed_ranges(almanac: &Almanac) -> Vec<(u64, u64)> {
+    let map = almanac.seed_to_location();
+    almanac
+        .seed_ranges()
+        .iter()
+        .flat_map(|(start, end)| map.map_range(*start, *end))
+        .collect()
+}
+
+/// Maps the range `cat_start..cat_end` with a map that is sorted by source start; the result may
+/// consist of several ranges.
+pub fn map_category_range(mut cat_start: u64, cat_end: u64, map: &[Map]) -> Vec<(u64, u64)> {
+    let mut destinations: Vec<(u64, u64)> = Vec::with_capacity(100);
+
+    for m in map {
+        let start = m.source_start;
+        let end = m.source_start + m.length;
+        // the maps are sorted by source_start, so if the category starts before the current
+        // mapping range, it is unmapped and thus taken over in the destination 1:1
+        if cat_start < start {
+            let new_cat_start = start.min(cat_end);
+            destinations.push((cat_start, new_cat_start));
+            cat_start = new_cat_start;
+        }
+        if cat_start >= cat_end {
+            break;
+        }
+        // If the end of the source sequence is before the
+        // start of the mapping range, we can end this loop here
+        if cat_end <= start {
+            break;
+        }
+        // if the start of the source sequence is after this mapping sequence, skip to the next map
+        if cat_start >= end {
+            continue;
+        }
+        let overlap_start = start.max(cat_start);
+        let overlap_end = end.min(cat_end);
+        destinations.push((
+            overlap_start - m.source_start + m.dest_start,
+            overlap_end - m.source_start + m.dest_start,
+        ));
+
+        cat_start = overlap_end;
+    }
+    // deal with possible leftover unmapped source range
+    if cat_start < cat_end {
+        destinations.push((cat_start, cat_end));
+    }
+
+    destinations
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const ALMANAC: &str = r#"seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+"#;
+
+    #[test]
+    fn map_seeds_works_for_example() {
+        // given
+        let almanac = parse(ALMANAC).expect("expected successful parsing");
+
+        // when
+        let mapped = map_seeds(&almanac);
+
+        // then
+        assert_eq!(&mapped, &[82, 43, 86, 35]);
+    }
+
+    #[test]
+    fn map_category_works_for_example() {
+        // given
+        let map = &[
+            Map {
+                dest_start: 50,
+                source_start: 98,
+                length: 2,
+            },
+            Map {
+                dest_start: 52,
+                source_start: 50,
+                length: 48,
+            },
+        ];
+        let seeds = &[79, 14, 55, 13];
+
+        // when
+        let soils: Vec<u64> = seeds
+            .iter()
+            .copied()
+            .map(|seed| map_category(seed, map))
+            .collect();
+
+        // then
+        assert_eq!(&soils, &[81, 14, 57, 13]);
+    }
+
+    #[test]
+    fn map_seed_ranges_works_for_example() {
+        // given
+        let almanac = parse(ALMANAC).expect("expected successful parsing");
+
+        // when
+        let mapped = map_seed_ranges(&almanac);
+
+        // then
+        assert_eq!(mapped.iter().map(|(start, _)| start).min(), Some(&46));
+    }
+
+    #[test]
+    fn almanac_maps_work_for_example() {
+        // given
+        let almanac = parse(ALMANAC).expect("expected successful parsing");
+
+        // when
+        let seed_to_location = almanac.seed_to_location();
+        let location_to_seed = almanac
+            .location_to_seed()
+            .expect("expected a bijective map");
+
+        // then
+        assert_eq!(location_to_seed.get(46), 82);
+        assert_eq!(seed_to_location.get(82), 46);
+        assert_eq!(seed_to_location.min_in(79, 93), Some(46));
+        assert_eq!(
+            location_to_seed.then(&seed_to_location),
+            IntervalMap::identity()
+        );
+    }
+
+    #[test]
+    fn parse_rejects_ranges_ending_after_the_largest_category() {
+        // given
+        let at_end = "seeds: 1\n\nseed-to-soil map:\n18446744073709551614 0 1\n";
+        let past_end = "seeds: 1\n\nseed-to-soil map:\n0 5 3\n18446744073709551614 0 2\n";
+
+        // when
+        let almanac = parse(at_end).expect("expected successful parsing");
+        let error = parse(past_end).expect_err("expected parsing to fail");
+
+        // then
+        assert_eq!(almanac.seed_to_location().get(0), u64::MAX - 1);
+        assert_eq!((error.line, error.column), (5, 1));
+        assert_eq!(
+            error.kind,
+            ErrorKind::Invalid("range ends after the largest category".to_owned())
+        );
+    }
+
+    #[test]
+    fn parse_map_reports_position_in_whole_input() {
+        // given
+        let input = ALMANAC.replacen("52 50 48", "52 5O 48", 1);
+
+        // when
+        let error = parse(&input).expect_err("expected parsing to fail");
+
+        // then
+        assert_eq!((error.line, error.column), (5, 4));
+        assert_eq!(error.text, "5O");
+    }
+}
@@ -0,0 +1,41 @@
+use crate::validate;
+use common::{read_input, Mode};
+use std::path::Path;
+
+/// The `--validate` mode of the binary: reports suspicious ranges and maps of each almanac, and
+/// fails for an almanac if any of them is not just a gap.
+#[derive(Default)]
+pub struct ValidateMode;
+
+impl Mode for ValidateMode {
+    fn flags(&self) -> &[&str] {
+        &["--validate"]
+    }
+
+    fn usage(&self) -> &str {
+        "--validate"
+    }
+
+    fn argument(
+        &mut self,
+        arg: &str,
+        _: &mut dyn Iterator<Item = &String>,
+    ) -> Result<bool, String> {
+        Ok(arg == "--validate")
+    }
+
+    fn run(&mut self, input: &Path, name: &str) -> Result<(), String> {
+        let content = read_input(input)?;
+        let issues = validate(&content).map_err(|e| e.to_string())?;
+        for issue in &issues {
+            println!("{name}, {issue}");
+        }
+        let errors = issues.iter().filter(|i| i.problem.is_error()).count();
+        println!("{name}: {errors} errors, {} gaps", issues.len() - errors);
+        if errors > 0 {
+            Err("the almanac is not valid".to_owned())
+        } else {
+            Ok(())
+        }
+    }
+}
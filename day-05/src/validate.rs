@@ -0,0 +1,265 @@
+use crate::{parse, parse_ranges, Map};
+use common::{split_once, ErrorKind, ParseError};
+use std::fmt;
+
+/// Something suspicious about a range or a map of an almanac.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Problem {
+    /// The range has the same source range as the one in the other line.
+    DuplicateSource { other_line: usize },
+    /// The source range overlaps the one in the other line, so some categories have two
+    /// destinations.
+    OverlappingSource { other_line: usize },
+    /// The destination range overlaps the one in the other line, so some categories are the
+    /// destination of two sources.
+    OverlappingDestination { other_line: usize },
+    /// Every source category of the range is mapped by ranges with a lower source start, so its
+    /// destination range is never used.
+    Unreachable,
+    /// No range maps the source categories `start..end` before this range, so they map to
+    /// themselves. This is common and only reported for completeness.
+    Gap { start: u64, end: u64 },
+    /// The map does not continue with the category the previous map ended with.
+    ChainOrder { expected: String, found: String },
+}
+
+impl Problem {
+    /// Whether the problem makes the almanac ambiguous or wrong, a gap does not.
+    pub fn is_error(&self) -> bool {
+        !matches!(self, Problem::Gap { .. })
+    }
+}
+
+/// A problem and the line of the almanac it was found in.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Issue {
+    pub line: usize,
+    pub problem: Problem,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "line {}: ", self.line)?;
+        match &self.problem {
+            Problem::DuplicateSource { other_line } => {
+                write!(f, "same source range as in line {other_line}")
+            }
+            Problem::OverlappingSource { other_line } => {
+                write!(f, "source range overlaps the one in line {other_line}")
+            }
+            Problem::OverlappingDestination { other_line } => {
+                write!(f, "destination range overlaps the one in line {other_line}")
+            }
+            Problem::Unreachable => write!(f, "destination range is unreachable"),
+            Problem::Gap { start, end } => {
+                write!(f, "source categories {start}..{end} are not mapped")
+            }
+            Problem::ChainOrder { expected, found } => {
+                write!(
+                    f,
+                    "expected a map from '{expected}', found one from '{found}'"
+                )
+            }
+        }
+    }
+}
+
+// a range of a map with the line it is in
+struct Range {
+    line: usize,
+    map: Map,
+}
+
+impl Range {
+    // ends are exclusive and may be u64::MAX + 1 or more
+    fn source_end(&self) -> u128 {
+        self.map.source_start as u128 + self.map.length as u128
+    }
+
+    fn dest_end(&self) -> u128 {
+        self.map.dest_start as u128 + self.map.length as u128
+    }
+}
+
+/// Checks an almanac for overlapping, duplicate and unreachable ranges, gaps between ranges and
+/// maps that are not in the order seed, …, location. Issues are ordered by line.
+pub fn validate(input: &str) -> Result<Vec<Issue>, ParseError> {
+    // syntax errors are reported like when solving the puzzle
+    parse(input)?;
+
+    let (seeds, maps) = split_once(input, input, "\n\n", "empty line between seeds and maps")?;
+    let mut issues: Vec<Issue> = Vec::with_capacity(16);
+    let mut expected = "seed";
+    // the seeds and the empty line after them come first
+    let mut header_line = seeds.lines().count() + 2;
+    for block in maps.split("\n\n") {
+        let header = block.lines().next().unwrap_or(block);
+        let (from, to) = header
+            .strip_suffix(" map:")
+            .and_then(|categories| categories.split_once("-to-"))
+            .ok_or_else(|| {
+                let kind = ErrorKind::Missing("map header like 'seed-to-soil map:'");
+                ParseError::at(header, header, kind).on_line(header_line)
+            })?;
+        if from != expected {
+            issues.push(Issue {
+                line: header_line,
+                problem: Problem::ChainOrder {
+                    expected: expected.to_owned(),
+                    found: from.to_owned(),
+                },
+            });
+        }
+        expected = to;
+
+        let mut ranges: Vec<Range> = parse_ranges(input, block)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, map)| Range {
+                line: header_line + 1 + i,
+                map,
+            })
+            .collect();
+        check_ranges(&mut ranges, &mut issues);
+        header_line += block.lines().count() + 1;
+    }
+    if expected != "location" {
+        issues.push(Issue {
+            line: input.lines().count(),
+            problem: Problem::ChainOrder {
+                expected: "location".to_owned(),
+                found: expected.to_owned(),
+            },
+        });
+    }
+
+    issues.sort_by_key(|issue| issue.line);
+    Ok(issues)
+}
+
+fn check_ranges(ranges: &mut [Range], issues: &mut Vec<Issue>) {
+    // every pair is reported once, at the later line
+    for (i, later) in ranges.iter().enumerate() {
+        for earlier in &ranges[..i] {
+            let other_line = earlier.line;
+            if later.map.source_start == earlier.map.source_start
+                && later.map.length == earlier.map.length
+            {
+                issues.push(Issue {
+                    line: later.line,
+                    problem: Problem::DuplicateSource { other_line },
+                });
+            } else if (later.map.source_start as u128) < earlier.source_end()
+                && (earlier.map.source_start as u128) < later.source_end()
+            {
+                issues.push(Issue {
+                    line: later.line,
+                    problem: Problem::OverlappingSource { other_line },
+                });
+            }
+            if (later.map.dest_start as u128) < earlier.dest_end()
+                && (earlier.map.dest_start as u128) < later.dest_end()
+            {
+                issues.push(Issue {
+                    line: later.line,
+                    problem: Problem::OverlappingDestination { other_line },
+                });
+            }
+        }
+    }
+
+    // like when solving, the range with the lower source start wins
+    ranges.sort_by_key(|range| range.map.source_start);
+    let mut covered_until: Option<u128> = None;
+    for range in ranges.iter().filter(|range| range.map.length > 0) {
+        match covered_until {
+            Some(end) if end >= range.source_end() => {
+                issues.push(Issue {
+                    line: range.line,
+                    problem: Problem::Unreachable,
+                });
+            }
+            Some(end) if end < range.map.source_start as u128 => {
+                issues.push(Issue {
+                    line: range.line,
+                    problem: Problem::Gap {
+                        // smaller than the source start, so it fits
+                        start: end as u64,
+                        end: range.map.source_start,
+                    },
+                });
+            }
+            _ => {}
+        }
+        covered_until = Some(covered_until.unwrap_or(0).max(range.source_end()));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn validate_accepts_valid_almanac() {
+        // given
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50 48\n\nsoil-to-location map:\n0 0 10\n";
+
+        // when
+        let issues = validate(input).expect("expected successful parsing");
+
+        // then
+        assert_eq!(issues, vec![]);
+    }
+
+    #[test]
+    fn validate_rejects_ranges_reaching_past_the_largest_category() {
+        // given
+        let input = "seeds: 79\n\nseed-to-location map:\n0 18446744073709551614 1\n5 18446744073709551614 2\n";
+
+        // when
+        let error = validate(input).expect_err("expected parsing to fail");
+
+        // then
+        assert_eq!((error.line, error.column), (5, 1));
+    }
+
+    #[test]
+    fn validate_reports_problems_with_lines() {
+        // given
+        let input = r#"seeds: 79 14
+
+seed-to-soil map:
+50 98 2
+52 50 48
+60 60 10
+0 98 2
+
+fertilizer-to-water map:
+0 10 5
+3 20 5
+
+water-to-light map:
+0 0 1
+"#;
+
+        // when
+        let issues = validate(input).expect("expected successful parsing");
+
+        // then
+        let issues: Vec<String> = issues.iter().map(|issue| issue.to_string()).collect();
+        assert_eq!(
+            issues,
+            vec![
+                "line 6: source range overlaps the one in line 5",
+                "line 6: destination range overlaps the one in line 5",
+                "line 6: destination range is unreachable",
+                "line 7: same source range as in line 4",
+                "line 7: destination range is unreachable",
+                "line 9: expected a map from 'soil', found one from 'fertilizer'",
+                "line 11: destination range overlaps the one in line 10",
+                "line 11: source categories 15..20 are not mapped",
+                "line 14: expected a map from 'location', found one from 'light'",
+            ]
+        );
+    }
+}
@@ -0,0 +1,305 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Shl, Shr, Sub};
+
+/// A non-negative integer of any size.
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct BigUint {
+    // base 2^32, least significant first, without leading zeros (so zero has no limbs)
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        BigUint::default()
+    }
+
+    fn from_limbs(mut limbs: Vec<u32>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+        BigUint { limbs }
+    }
+
+    /// Parses a number of decimal digits, `None` if there are none or something else.
+    pub fn from_decimal(digits: &str) -> Option<Self> {
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        // nine digits at a time still fit into a limb
+        let mut n = BigUint::zero();
+        for chunk in digits.as_bytes().chunks(9) {
+            let value = chunk
+                .iter()
+                .fold(0u32, |value, digit| value * 10 + (digit - b'0') as u32);
+            n = n.mul_add_small(10u32.pow(chunk.len() as u32), value);
+        }
+        Some(n)
+    }
+
+    // self * factor + summand
+    fn mul_add_small(&self, factor: u32, summand: u32) -> Self {
+        let mut limbs: Vec<u32> = Vec::with_capacity(self.limbs.len() + 1);
+        let mut carry = summand as u64;
+        for limb in &self.limbs {
+            let value = *limb as u64 * factor as u64 + carry;
+            limbs.push(value as u32);
+            carry = value >> 32;
+        }
+        limbs.push(carry as u32);
+        BigUint::from_limbs(limbs)
+    }
+
+    // the quotient and remainder of a division by a small divisor
+    fn div_rem_small(&self, divisor: u32) -> (Self, u32) {
+        let mut limbs = vec![0u32; self.limbs.len()];
+        let mut rem = 0u64;
+        for (i, limb) in self.limbs.iter().enumerate().rev() {
+            let value = (rem << 32) | *limb as u64;
+            limbs[i] = (value / divisor as u64) as u32;
+            rem = value % divisor as u64;
+        }
+        (BigUint::from_limbs(limbs), rem as u32)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.limbs.first().is_some_and(|limb| limb & 1 == 1)
+    }
+
+    fn bits(&self) -> u64 {
+        match self.limbs.last() {
+            Some(last) => self.limbs.len() as u64 * 32 - last.leading_zeros() as u64,
+            None => 0,
+        }
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.limbs.len() > 4 {
+            return None;
+        }
+        Some(
+            self.limbs
+                .iter()
+                .rev()
+                .fold(0u128, |n, limb| (n << 32) | *limb as u128),
+        )
+    }
+
+    /// The subtraction, `None` if the result would be negative.
+    pub fn checked_sub(&self, other: &BigUint) -> Option<Self> {
+        if self < other {
+            return None;
+        }
+        let mut limbs: Vec<u32> = Vec::with_capacity(self.limbs.len());
+        let mut borrow = 0i64;
+        for (i, limb) in self.limbs.iter().enumerate() {
+            let mut value = *limb as i64 - other.limbs.get(i).copied().unwrap_or(0) as i64 - borrow;
+            borrow = 0;
+            if value < 0 {
+                value += 1 << 32;
+                borrow = 1;
+            }
+            limbs.push(value as u32);
+        }
+        Some(BigUint::from_limbs(limbs))
+    }
+
+    /// The largest integer whose square is at most this number, computed bit by bit without any
+    /// floating point arithmetic.
+    pub fn isqrt(&self) -> Self {
+        let mut rest = self.clone();
+        let mut root = BigUint::zero();
+        // the highest power of four that is at most the number
+        let mut bit = if self.is_zero() {
+            BigUint::zero()
+        } else {
+            &BigUint::from(1) << ((self.bits() - 1) & !1)
+        };
+        while !bit.is_zero() {
+            let candidate = &root + &bit;
+            if let Some(smaller) = rest.checked_sub(&candidate) {
+                rest = smaller;
+                root = &(&root >> 1) + &bit;
+            } else {
+                root = &root >> 1;
+            }
+            bit = &bit >> 2;
+        }
+        root
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(n: u64) -> Self {
+        BigUint::from_limbs(vec![n as u32, (n >> 32) as u32])
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let len = self.limbs.len().max(other.limbs.len());
+        let mut limbs: Vec<u32> = Vec::with_capacity(len + 1);
+        let mut carry = 0u64;
+        for i in 0..len {
+            let value = self.limbs.get(i).copied().unwrap_or(0) as u64
+                + other.limbs.get(i).copied().unwrap_or(0) as u64
+                + carry;
+            limbs.push(value as u32);
+            carry = value >> 32;
+        }
+        limbs.push(carry as u32);
+        BigUint::from_limbs(limbs)
+    }
+}
+
+/// Panics if the result would be negative, see [`BigUint::checked_sub`].
+impl Sub for &BigUint {
+    type Output = BigUint;
+
+    fn sub(self, other: &BigUint) -> BigUint {
+        self.checked_sub(other)
+            .expect("subtraction of a larger number")
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, b) in other.limbs.iter().enumerate() {
+                let value = *a as u64 * *b as u64 + limbs[i + j] as u64 + carry;
+                limbs[i + j] = value as u32;
+                carry = value >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Shl<u64> for &BigUint {
+    type Output = BigUint;
+
+    fn shl(self, bits: u64) -> BigUint {
+        let (whole, part) = ((bits / 32) as usize, bits % 32);
+        let mut limbs = vec![0u32; whole];
+        let mut carry = 0u32;
+        for limb in &self.limbs {
+            limbs.push((limb << part) | carry);
+            carry = if part == 0 { 0 } else { limb >> (32 - part) };
+        }
+        limbs.push(carry);
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Shr<u64> for &BigUint {
+    type Output = BigUint;
+
+    fn shr(self, bits: u64) -> BigUint {
+        let (whole, part) = ((bits / 32) as usize, bits % 32);
+        let limbs = self.limbs.get(whole..).unwrap_or(&[]);
+        let shifted = (0..limbs.len())
+            .map(|i| {
+                let high = if part == 0 {
+                    0
+                } else {
+                    limbs.get(i + 1).copied().unwrap_or(0) << (32 - part)
+                };
+                (limbs[i] >> part) | high
+            })
+            .collect();
+        BigUint::from_limbs(shifted)
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        // groups of nine decimal digits, least significant first
+        let mut groups: Vec<u32> = Vec::with_capacity(self.limbs.len() * 32 / 29 + 1);
+        let mut n = self.clone();
+        while !n.is_zero() {
+            let (quotient, group) = n.div_rem_small(1_000_000_000);
+            groups.push(group);
+            n = quotient;
+        }
+        let mut groups = groups.iter().rev();
+        if let Some(first) = groups.next() {
+            write!(f, "{first}")?;
+        }
+        for group in groups {
+            write!(f, "{group:09}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn big(digits: &str) -> BigUint {
+        BigUint::from_decimal(digits).expect("expected a valid number")
+    }
+
+    #[test]
+    fn arithmetic_works() {
+        // given
+        let a = big("123456789012345678901234567890");
+        let b = big("987654321098765432109876543210");
+
+        // then
+        assert_eq!((&a + &b).to_string(), "1111111110111111111011111111100");
+        assert_eq!((&b - &a).to_string(), "864197532086419753208641975320");
+        assert_eq!(
+            (&a * &b).to_string(),
+            "121932631137021795226185032733622923332237463801111263526900"
+        );
+        assert_eq!((&(&a << 70) >> 70), a);
+        assert_eq!(a.checked_sub(&b), None);
+        assert_eq!(BigUint::from(u64::MAX).to_string(), u64::MAX.to_string());
+        assert_eq!(big("0000").to_string(), "0");
+        assert_eq!(BigUint::from_decimal("12a"), None);
+    }
+
+    #[test]
+    fn isqrt_works() {
+        for n in 0..2000u64 {
+            assert_eq!(BigUint::from(n).isqrt(), BigUint::from(n.isqrt()));
+        }
+        let root = big("31415926535897932384626433832795028841971");
+        let square = &root * &root;
+        assert_eq!(square.isqrt(), root);
+        assert_eq!(
+            (&square - &BigUint::from(1)).isqrt(),
+            &root - &BigUint::from(1)
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! Day 6: Wait For It
+//!
+//! Boat races, which can be won by holding the button of the toy boat for the right time.
+
+use common::{parse_number, split_once, Answer, ErrorKind, ParseError, Solution};
+
+mod bigint;
+pub use bigint::BigUint;
+
+/// Solves both parts of the puzzle, see [`Solution`].
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 6;
+
+    type Input<'a> = Vec<(u64, u64)>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Answer {
+        ways_to_win_prod(input).into()
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Answer {
+        let (time, distance) = fix_bad_kerning_exact(input);
+        let ways = winning_holds(&time, &distance)
+            .map(|holds| holds.count())
+            .unwrap_or_default();
+        match ways.to_u128() {
+            Some(ways) => ways.into(),
+            None => ways.to_string().into(),
+        }
+    }
+}
+
+/// Parses the races as pairs of time and record distance.
+pub fn parse(input: &str) -> Result<Vec<(u64, u64)>, ParseError> {
+    let (time, distance) = split_once(input, input, "\n", "second line")?;
+    let distance = distance.lines().next().unwrap_or(distance);
+    let times = time
+        .strip_prefix("Time:")
+        .ok_or_else(|| ParseError::at(input, time, ErrorKind::Missing("prefix 'Time:'")))?
+        .split_whitespace()
+        .map(|s| parse_number(input, s))
+        .collect::<Result<Vec<u64>, ParseError>>()?;
+    let distances = distance
+        .strip_prefix("Distance:")
+        .ok_or_else(|| ParseError::at(input, distance, ErrorKind::Missing("prefix 'Distance:'")))?
+        .split_whitespace()
+        .map(|s| parse_number(input, s))
+        .collect::<Result<Vec<u64>, ParseError>>()?;
+
+    if times.len() != distances.len() {
+        let kind = ErrorKind::Invalid(format!(
+            "expected {} distances, one for each time, found {}",
+            times.len(),
+            distances.len()
+        ));
+        return Err(ParseError::at(input, distance, kind));
+    }
+    Ok(times.into_iter().zip(distances).collect())
+}
+
+/// Joins the times and distances of all races into one big race, as if the spaces were not there.
+/// The result overflows for long inputs, see [`fix_bad_kerning_exact`].
+pub fn fix_bad_kerning(races: &[(u64, u64)]) -> (u64, u64) {
+    races.iter().fold((0, 0), |(t, d), (part_t, part_d)| {
+        (
+            t * 10u64.pow(part_t.ilog10() + 1) + part_t,
+            d * 10u64.pow(part_d.ilog10() + 1) + part_d,
+        )
+    })
+}
+
+/// The product of the number of ways to win each race.
+pub fn ways_to_win_prod(races: &[(u64, u64)]) -> u64 {
+    races
+        .iter()
+        .map(|(time, distance)| ways_to_win(*time, *distance))
+        .product()
+}
+
+/// Joins the times and distances of all races into one big race, without any limit on the size of
+/// the result.
+pub fn fix_bad_kerning_exact(races: &[(u64, u64)]) -> (BigUint, BigUint) {
+    let join = |numbers: &mut dyn Iterator<Item = u64>| {
+        let digits: String = numbers.map(|n| n.to_string()).collect();
+        BigUint::from_decimal(&digits).unwrap_or_default()
+    };
+    (
+        join(&mut races.iter().map(|(time, _)| *time)),
+        join(&mut races.iter().map(|(_, distance)| *distance)),
+    )
+}
+
+/// The hold times that beat the record, from `first` to `last` (inclusive).
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct WinningHolds {
+    pub first: BigUint,
+    pub last: BigUint,
+}
+
+impl WinningHolds {
+    /// The number of hold times that beat the record.
+    pub fn count(&self) -> BigUint {
+        &(&self.last - &self.first) + &BigUint::from(1)
+    }
+}
+
+/// The hold times that beat the record `distance` in a race that lasts `time` milliseconds, `None`
+/// if there are none.
+pub fn winning_holds(time: &BigUint, distance: &BigUint) -> Option<WinningHolds> {
+    // Holding for h wins if h * (time - h) > distance, i.e. if (2h - time)^2 < time^2 - 4 distance.
+    // So the winning holds are those with |2h - time| <= x for the largest x that has the parity of
+    // time and a square below the discriminant.
+    let discriminant = (time * time).checked_sub(&(distance << 2))?;
+    let root = discriminant.isqrt();
+    let mut x = if &root * &root == discriminant {
+        root.checked_sub(&BigUint::from(1))?
+    } else {
+        root
+    };
+    if x.is_odd() != time.is_odd() {
+        x = x.checked_sub(&BigUint::from(1))?;
+    }
+    // x <= time, so this can not fail
+    let first = &time.checked_sub(&x)? >> 1;
+    let last = &(time + &x) >> 1;
+    Some(WinningHolds { first, last })
+}
+
+/// The number of ways to beat the record `distance` in a race that lasts `time` milliseconds.
+pub fn ways_to_win(time: u64, distance: u64) -> u64 {
+    winning_holds(&time.into(), &distance.into())
+        .and_then(|holds| holds.count().to_u128())
+        .unwrap_or(0) as u64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const RACES: &str = r#"Time:      7  15   30
+Distance:  9  40  200
+"#;
+
+    #[test]
+    fn ways_to_win_prod_works_for_example() {
+        // given
+        let races = parse(RACES).expect("expected successful parsing");
+
+        // when
+        let result = ways_to_win_prod(&races);
+
+        // then
+        assert_eq!(result, 288);
+    }
+
+    #[test]
+    fn fix_bad_kerning_works_for_example() {
+        // given
+        let races = parse(RACES).expect("expected successful parsing");
+
+        // when
+        let (t, d) = fix_bad_kerning(&races);
+
+        // then
+        assert_eq!(t, 71530);
+        assert_eq!(d, 940200);
+    }
+
+    #[test]
+    fn ways_to_win_works_for_examples() {
+        assert_eq!(ways_to_win(7, 9), 4);
+        assert_eq!(ways_to_win(15, 40), 8);
+        assert_eq!(ways_to_win(30, 200), 9);
+        assert_eq!(ways_to_win(71530, 940200), 71503);
+        assert_eq!(ways_to_win(10, 25), 0);
+        assert_eq!(ways_to_win(10, 24), 1);
+        assert_eq!(ways_to_win(3, 10), 0);
+    }
+
+    #[test]
+    fn winning_holds_works_for_long_inputs() {
+        // given
+        let races: Vec<(u64, u64)> = [(7, 9), (15, 40), (30, 200)].repeat(10);
+
+        // when
+        let (time, distance) = fix_bad_kerning_exact(&races);
+        let holds = winning_holds(&time, &distance).expect("expected a way to win");
+
+        // then
+        assert_eq!(time.to_string(), "71530".repeat(10));
+        let before = &holds.first - &BigUint::from(1);
+        let wins = |h: &BigUint| h * &(&time - h) > distance;
+        assert!(wins(&holds.first));
+        assert!(wins(&holds.last));
+        assert!(!wins(&before));
+        assert!(!wins(&(&holds.last + &BigUint::from(1))));
+        assert_eq!(&holds.first + &holds.last, time);
+    }
+}
@@ -0,0 +1,221 @@
+//! Day 7: Camel Cards
+//!
+//! Hands of camel cards ranked by their type and cards, with and without jokers, or with any
+//! other [`Rules`].
+
+mod mode;
+mod rules;
+mod stats;
+
+pub use mode::{CompleteMode, RulesMode};
+pub use rules::{parse_hands, total_winnings_with, Category, Rules, Strength, CARDS, HAND_TYPES};
+pub use stats::{completions, parse_partial, statistics, Statistics};
+
+use common::{parse_lines, parse_number, split_once, Answer, ErrorKind, ParseError, Solution};
+use std::cmp::Ordering;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// Solves both parts of the puzzle, see [`Solution`].
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 7;
+
+    type Input<'a> = Vec<(Hand, u32)>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Answer {
+        total_winnings(input.clone()).into()
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Answer {
+        total_winnings_joker(input.clone()).into()
+    }
+}
+
+/// The types of hands, from the weakest to the strongest.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum HandType {
+    High,
+    Pair,
+    TwoPair,
+    Three,
+    FullHouse,
+    Four,
+    Five,
+}
+
+impl fmt::Display for HandType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        let name = match self {
+            HandType::High => "high card",
+            HandType::Pair => "one pair",
+            HandType::TwoPair => "two pair",
+            HandType::Three => "three of a kind",
+            HandType::FullHouse => "full house",
+            HandType::Four => "four of a kind",
+            HandType::Five => "five of a kind",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The cards, from the weakest to the strongest (without jokers).
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash, Debug)]
+pub enum Card {
+    Two = 0,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    T,
+    J,
+    Q,
+    K,
+    A,
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "{}", b"23456789TJQKA"[*self as usize] as char)
+    }
+}
+
+/// The card for one of the characters `2`-`9`, `T`, `J`, `Q`, `K` or `A`.
+pub fn card_from_byte(c: u8) -> Result<Card, ErrorKind> {
+    match c {
+        b'2' => Ok(Card::Two),
+        b'3' => Ok(Card::Three),
+        b'4' => Ok(Card::Four),
+        b'5' => Ok(Card::Five),
+        b'6' => Ok(Card::Six),
+        b'7' => Ok(Card::Seven),
+        b'8' => Ok(Card::Eight),
+        b'9' => Ok(Card::Nine),
+        b'T' => Ok(Card::T),
+        b'J' => Ok(Card::J),
+        b'Q' => Ok(Card::Q),
+        b'K' => Ok(Card::K),
+        b'A' => Ok(Card::A),
+        _ => Err(ErrorKind::Unknown("card")),
+    }
+}
+
+// the rules of both parts, built only once because hands are compared with them while sorting
+fn standard_rules() -> &'static Rules {
+    static RULES: OnceLock<Rules> = OnceLock::new();
+    RULES.get_or_init(Rules::standard)
+}
+
+fn joker_rules() -> &'static Rules {
+    static RULES: OnceLock<Rules> = OnceLock::new();
+    RULES.get_or_init(Rules::joker)
+}
+
+/// A hand of five cards, ordered by its type and then card by card.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Hand(pub [Card; 5]);
+
+impl Hand {
+    /// The type of the hand.
+    pub fn hand_type(&self) -> HandType {
+        standard_rules().hand_type(&self.0)
+    }
+
+    /// The best type the hand can have if every `J` is a joker that can stand for any card.
+    pub fn hand_type_joker(&self) -> HandType {
+        joker_rules().hand_type(&self.0)
+    }
+}
+
+impl AsRef<[Card]> for Hand {
+    fn as_ref(&self) -> &[Card] {
+        &self.0
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.hand_type()
+            .cmp(&other.hand_type())
+            .then_with(|| self.0.cmp(&other.0))
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Parses one hand and its bid per line.
+pub fn parse(input: &str) -> Result<Vec<(Hand, u32)>, ParseError> {
+    parse_lines(input, |line| {
+        let (hand, bid) = split_once(line, line, " ", "separator between hand and bid")?;
+        let bid: u32 = parse_number(line, bid)?;
+        if hand.len() != 5 {
+            let kind = ErrorKind::Invalid("expected exactly 5 cards in a hand".to_owned());
+            return Err(ParseError::at(line, hand, kind));
+        }
+        let mut cards = [Card::Two; 5];
+        for (i, card) in cards.iter_mut().enumerate() {
+            *card = card_from_byte(hand.as_bytes()[i])
+                .map_err(|kind| ParseError::at(line, hand.get(i..i + 1).unwrap_or(hand), kind))?;
+        }
+
+        Ok((Hand(cards), bid))
+    })
+}
+
+/// Sums up the bid of every hand multiplied with its rank.
+pub fn total_winnings(hands: Vec<(Hand, u32)>) -> usize {
+    total_winnings_with(&hands, standard_rules())
+}
+
+/// Like [`total_winnings`], but with jokers.
+pub fn total_winnings_joker(hands: Vec<(Hand, u32)>) -> usize {
+    total_winnings_with(&hands, joker_rules())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const HANDS: &str = r#"32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483
+"#;
+
+    #[test]
+    fn total_winnings_works_for_example() {
+        // given
+        let hands = parse(HANDS).expect("expected successful parsing");
+
+        // when
+        let winnings = total_winnings(hands);
+
+        // then
+        assert_eq!(winnings, 6440);
+    }
+
+    #[test]
+    fn total_winnings_joker_works_for_example() {
+        // given
+        let hands = parse(HANDS).expect("expected successful parsing");
+
+        // when
+        let winnings = total_winnings_joker(hands);
+
+        // then
+        assert_eq!(winnings, 5905);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{parse_hands, parse_partial, statistics, total_winnings_with, Rules};
+use common::{input_name, option_value, read_input, Mode};
+use std::path::{Path, PathBuf};
+
+/// The `--rules` mode of the binary: prints the total winnings of each input with the rules from a
+/// config file, see [`Rules::from_config`].
+#[derive(Default)]
+pub struct RulesMode {
+    config: PathBuf,
+    rules: Option<Rules>,
+}
+
+impl Mode for RulesMode {
+    fn flags(&self) -> &[&str] {
+        &["--rules"]
+    }
+
+    fn usage(&self) -> &str {
+        "--rules <config file>"
+    }
+
+    fn argument(
+        &mut self,
+        arg: &str,
+        args: &mut dyn Iterator<Item = &String>,
+    ) -> Result<bool, String> {
+        match arg {
+            "--rules" => self.config = PathBuf::from(option_value(args, arg)?),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn start(&mut self) -> Result<(), String> {
+        let rules = Rules::from_config(&read_input(&self.config)?)
+            .map_err(|e| format!("{}: {e}", input_name(&self.config)))?;
+        self.rules = Some(rules);
+        Ok(())
+    }
+
+    fn run(&mut self, input: &Path, name: &str) -> Result<(), String> {
+        let rules = self.rules.as_ref().ok_or("missing rules")?;
+        let hands = parse_hands(&read_input(input)?).map_err(|e| e.to_string())?;
+        println!("{name}: {}", total_winnings_with(&hands, rules));
+        Ok(())
+    }
+}
+
+/// The `--complete` mode of the binary: prints how all completions of each partially known hand
+/// are distributed over the hand types, with and without jokers, and the best completion.
+#[derive(Default)]
+pub struct CompleteMode {
+    hands: Vec<String>,
+}
+
+impl Mode for CompleteMode {
+    fn flags(&self) -> &[&str] {
+        &["--complete"]
+    }
+
+    fn usage(&self) -> &str {
+        "--complete <hand with ? for unknown cards>..."
+    }
+
+    fn argument(
+        &mut self,
+        arg: &str,
+        _: &mut dyn Iterator<Item = &String>,
+    ) -> Result<bool, String> {
+        match arg {
+            "--complete" => {}
+            hand if !hand.starts_with("--") => self.hands.push(hand.to_owned()),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn reads_inputs(&self) -> bool {
+        false
+    }
+
+    fn start(&mut self) -> Result<(), String> {
+        if self.hands.is_empty() {
+            return Err(format!("missing hand\nusage: {}", self.usage()));
+        }
+        for hand in &self.hands {
+            let partial = parse_partial(hand).map_err(|e| format!("{hand}: {e}"))?;
+            for (name, rules) in [("standard", Rules::standard()), ("joker", Rules::joker())] {
+                let stats = statistics(&partial, &rules)
+                    .ok_or_else(|| format!("{hand}: expected {} cards", rules.hand_size()))?;
+                println!("{hand} ({name} rules): {} completions", stats.completions);
+                for (hand_type, count) in stats.distribution().filter(|(_, count)| *count > 0) {
+                    let share = stats.share(hand_type) * 100.0;
+                    println!("  {hand_type}: {count} ({share:.2}%)");
+                }
+                let best: String = stats.best.iter().map(|card| card.to_string()).collect();
+                println!("  best: {best} ({})", stats.best_type);
+            }
+        }
+        Ok(())
+    }
+
+    // the hands are given as arguments, see `start`
+    fn run(&mut self, _: &Path, _: &str) -> Result<(), String> {
+        Ok(())
+    }
+}
@@ -0,0 +1,428 @@
+use crate::{card_from_byte, Card, HandType};
+use common::{parse_lines, parse_number, split_once, ErrorKind, ParseError};
+
+/// All cards, from the weakest to the strongest without jokers.
+pub const CARDS: [Card; 13] = [
+    Card::Two,
+    Card::Three,
+    Card::Four,
+    Card::Five,
+    Card::Six,
+    Card::Seven,
+    Card::Eight,
+    Card::Nine,
+    Card::T,
+    Card::J,
+    Card::Q,
+    Card::K,
+    Card::A,
+];
+
+/// All hand types, from the weakest to the strongest.
+pub const HAND_TYPES: [HandType; 7] = [
+    HandType::High,
+    HandType::Pair,
+    HandType::TwoPair,
+    HandType::Three,
+    HandType::FullHouse,
+    HandType::Four,
+    HandType::Five,
+];
+
+impl HandType {
+    // how many cards of the most and the second most common kind the type needs
+    fn needs(self) -> (u8, u8) {
+        match self {
+            HandType::High => (0, 0),
+            HandType::Pair => (2, 0),
+            HandType::TwoPair => (2, 2),
+            HandType::Three => (3, 0),
+            HandType::FullHouse => (3, 2),
+            HandType::Four => (4, 0),
+            HandType::Five => (5, 0),
+        }
+    }
+}
+
+// Whether cards with the given counts of the most and the second most common kind have the type,
+// if each wildcard can stand for any card.
+fn has_type(hand_type: HandType, (first, second): (u8, u8), wildcards: u8) -> bool {
+    let (need_first, need_second) = hand_type.needs();
+    need_first.saturating_sub(first) + need_second.saturating_sub(second) <= wildcards
+}
+
+/// The strongest type cards with the given counts of the most and the second most common kind
+/// have, if each wildcard can stand for any card.
+pub(crate) fn best_type(counts: (u8, u8), wildcards: u8) -> HandType {
+    HAND_TYPES
+        .iter()
+        .rev()
+        .copied()
+        .find(|hand_type| has_type(*hand_type, counts, wildcards))
+        .unwrap_or(HandType::High)
+}
+
+/// A category of hands a rule set ranks.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Category {
+    /// Hands with cards of the same kind like the hand type, e.g. a full house.
+    Kind(HandType),
+    /// Hands whose cards are all different and follow each other in the card order of the rules.
+    Straight,
+    /// Hands whose cards are all from this set, like a flush.
+    AllOf(Vec<Card>),
+}
+
+/// A variant of Camel Cards: the order of the cards, which cards are wildcards that can stand for
+/// any other card, the number of cards in a hand and the categories of hands from the weakest to
+/// the strongest. A hand belongs to the strongest category it fits into, hands of the same
+/// category are ordered card by card.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Rules {
+    // the rank of each card, indexed by `Card as usize`
+    ranks: [u8; 13],
+    wildcards: Vec<Card>,
+    hand_size: usize,
+    categories: Vec<Category>,
+}
+
+/// How strong a hand is with a rule set, stronger hands are greater.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Strength {
+    /// The index of the category in the rules.
+    pub category: usize,
+    /// The rank of each card, for hands of the same category.
+    pub ranks: Vec<u8>,
+}
+
+impl Rules {
+    /// The rules of part one.
+    pub fn standard() -> Self {
+        Rules {
+            ranks: std::array::from_fn(|i| i as u8),
+            wildcards: vec![],
+            hand_size: 5,
+            categories: HAND_TYPES.iter().copied().map(Category::Kind).collect(),
+        }
+    }
+
+    /// The rules of part two: `J` is the weakest card and a wildcard.
+    pub fn joker() -> Self {
+        Rules::standard()
+            .with_order(&[Card::J])
+            .expect("expected a valid card order")
+            .with_wildcards(&[Card::J])
+    }
+
+    /// These rules with the given cards as the weakest ones, in the given order. The other cards
+    /// follow in their standard order.
+    pub fn with_order(mut self, weakest: &[Card]) -> Result<Self, String> {
+        let order: Vec<Card> = weakest
+            .iter()
+            .copied()
+            .chain(CARDS.iter().copied().filter(|card| !weakest.contains(card)))
+            .collect();
+        if order.len() != CARDS.len() {
+            return Err("every card may only appear once in the card order".to_owned());
+        }
+        for (rank, card) in order.iter().enumerate() {
+            self.ranks[*card as usize] = rank as u8;
+        }
+        Ok(self)
+    }
+
+    /// These rules with cards that can stand for any other card.
+    pub fn with_wildcards(mut self, wildcards: &[Card]) -> Self {
+        self.wildcards = wildcards.to_vec();
+        self
+    }
+
+    /// These rules for hands of the given number of cards.
+    pub fn with_hand_size(mut self, hand_size: usize) -> Result<Self, String> {
+        if hand_size == 0 || hand_size > u8::MAX as usize {
+            return Err(format!("invalid hand size {hand_size}"));
+        }
+        self.hand_size = hand_size;
+        Ok(self)
+    }
+
+    /// The categories of hands from the weakest to the strongest, hands that fit into no
+    /// category are weaker than all others.
+    pub fn with_categories(mut self, categories: Vec<Category>) -> Result<Self, String> {
+        if categories.is_empty() {
+            return Err("there must be at least one category".to_owned());
+        }
+        self.categories = categories;
+        Ok(self)
+    }
+
+    pub fn hand_size(&self) -> usize {
+        self.hand_size
+    }
+
+    pub fn categories(&self) -> &[Category] {
+        &self.categories
+    }
+
+    pub fn is_wildcard(&self, card: Card) -> bool {
+        self.wildcards.contains(&card)
+    }
+
+    pub fn rank(&self, card: Card) -> u8 {
+        self.ranks[card as usize]
+    }
+
+    // the counts of all cards that are not wildcards, and the number of wildcards
+    fn counts(&self, cards: &[Card]) -> ([u8; 13], u8) {
+        let mut counts = [0u8; 13];
+        let mut wildcards = 0;
+        for card in cards {
+            if self.is_wildcard(*card) {
+                wildcards += 1;
+            } else {
+                counts[*card as usize] += 1;
+            }
+        }
+        (counts, wildcards)
+    }
+
+    /// The strongest type of the cards, if each wildcard stands for the best card.
+    pub fn hand_type(&self, cards: &[Card]) -> HandType {
+        let (mut counts, wildcards) = self.counts(cards);
+        counts.sort_unstable_by(|l, r| r.cmp(l));
+        best_type((counts[0], counts[1]), wildcards)
+    }
+
+    /// Whether the cards fit into the category, if each wildcard stands for the best card.
+    pub fn fits(&self, cards: &[Card], category: &Category) -> bool {
+        let (counts, wildcards) = self.counts(cards);
+        match category {
+            Category::Kind(hand_type) => {
+                let mut sorted = counts;
+                sorted.sort_unstable_by(|l, r| r.cmp(l));
+                has_type(*hand_type, (sorted[0], sorted[1]), wildcards)
+            }
+            Category::Straight => {
+                let ranks: Vec<u8> = CARDS
+                    .iter()
+                    .filter(|card| counts[**card as usize] > 0)
+                    .map(|card| self.rank(*card))
+                    .collect();
+                let distinct = counts.iter().all(|count| *count <= 1);
+                let span = match (ranks.iter().min(), ranks.iter().max()) {
+                    (Some(min), Some(max)) => (max - min) as usize + 1,
+                    _ => 0,
+                };
+                distinct && span <= cards.len() && cards.len() <= CARDS.len()
+            }
+            Category::AllOf(set) => CARDS
+                .iter()
+                .all(|card| counts[*card as usize] == 0 || set.contains(card)),
+        }
+    }
+
+    /// How strong the cards are, `None` if they have the wrong number of cards.
+    pub fn strength(&self, cards: &[Card]) -> Option<Strength> {
+        if cards.len() != self.hand_size {
+            return None;
+        }
+        let category = self
+            .categories
+            .iter()
+            .rposition(|category| self.fits(cards, category))
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        Some(Strength {
+            category,
+            ranks: cards.iter().map(|card| self.rank(*card)).collect(),
+        })
+    }
+
+    /// Parses rules from a configuration like this, every line is optional:
+    ///
+    /// ```text
+    /// order: J23456789TQKA
+    /// wildcards: J
+    /// hand size: 5
+    /// categories: high pair two-pair three full-house four five
+    /// ```
+    ///
+    /// The order lists the cards from the weakest to the strongest, missing cards are stronger in
+    /// their standard order. Categories are `high`, `pair`, `two-pair`, `three`, `full-house`,
+    /// `four`, `five`, `straight` and `all-of:<cards>` (e.g. `all-of:TJQKA`).
+    pub fn from_config(config: &str) -> Result<Self, ParseError> {
+        let mut rules = Rules::standard();
+        let settings = parse_lines(config, |line| {
+            if line.trim().is_empty() || line.starts_with('#') {
+                return Ok(None);
+            }
+            let (key, value) = split_once(line, line, ": ", "': ' after the setting")?;
+            Ok(Some((line, key, value)))
+        })?;
+        for (i, setting) in settings.into_iter().enumerate() {
+            let Some((line, key, value)) = setting else {
+                continue;
+            };
+            let invalid = |reason: String| {
+                ParseError::at(line, value, ErrorKind::Invalid(reason)).on_line(i + 1)
+            };
+            rules =
+                match key {
+                    "order" => {
+                        rules.with_order(&parse_cards(line, value).map_err(|e| e.on_line(i + 1))?)
+                    }
+                    "wildcards" => Ok(rules
+                        .with_wildcards(&parse_cards(line, value).map_err(|e| e.on_line(i + 1))?)),
+                    "hand size" => rules.with_hand_size(
+                        parse_number(line, value).map_err(|e: ParseError| e.on_line(i + 1))?,
+                    ),
+                    "categories" => {
+                        let categories = value
+                            .split_whitespace()
+                            .map(|category| parse_category(line, category))
+                            .collect::<Result<Vec<_>, ParseError>>()
+                            .map_err(|e| e.on_line(i + 1))?;
+                        rules.with_categories(categories)
+                    }
+                    _ => {
+                        let kind = ErrorKind::Unknown("setting");
+                        return Err(ParseError::at(line, key, kind).on_line(i + 1));
+                    }
+                }
+                .map_err(invalid)?;
+        }
+        Ok(rules)
+    }
+}
+
+fn parse_cards(line: &str, cards: &str) -> Result<Vec<Card>, ParseError> {
+    cards
+        .bytes()
+        .enumerate()
+        .map(|(i, c)| {
+            card_from_byte(c)
+                .map_err(|kind| ParseError::at(line, cards.get(i..i + 1).unwrap_or(cards), kind))
+        })
+        .collect()
+}
+
+fn parse_category(line: &str, category: &str) -> Result<Category, ParseError> {
+    Ok(match category {
+        "high" => Category::Kind(HandType::High),
+        "pair" => Category::Kind(HandType::Pair),
+        "two-pair" => Category::Kind(HandType::TwoPair),
+        "three" => Category::Kind(HandType::Three),
+        "full-house" => Category::Kind(HandType::FullHouse),
+        "four" => Category::Kind(HandType::Four),
+        "five" => Category::Kind(HandType::Five),
+        "straight" => Category::Straight,
+        _ => match category.strip_prefix("all-of:") {
+            Some(cards) => Category::AllOf(parse_cards(line, cards)?),
+            None => {
+                return Err(ParseError::at(
+                    line,
+                    category,
+                    ErrorKind::Unknown("category"),
+                ))
+            }
+        },
+    })
+}
+
+/// Sums up the bid of every hand multiplied with its rank, with the given rules. Hands with the
+/// wrong number of cards are the weakest.
+pub fn total_winnings_with<H: AsRef<[Card]>>(hands: &[(H, u32)], rules: &Rules) -> usize {
+    let mut ranked: Vec<(Option<Strength>, u32)> = hands
+        .iter()
+        .map(|(hand, bid)| (rules.strength(hand.as_ref()), *bid))
+        .collect();
+    ranked.sort_unstable();
+    ranked
+        .iter()
+        .enumerate()
+        .map(|(i, (_, bid))| (i + 1) * *bid as usize)
+        .sum()
+}
+
+/// Parses one hand and its bid per line, with hands of any size.
+pub fn parse_hands(input: &str) -> Result<Vec<(Vec<Card>, u32)>, ParseError> {
+    parse_lines(input, |line| {
+        let (hand, bid) = split_once(line, line, " ", "separator between hand and bid")?;
+        let bid: u32 = parse_number(line, bid)?;
+        Ok((parse_cards(line, hand)?, bid))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{parse, total_winnings, Hand};
+
+    const HANDS: &str = r#"32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483
+"#;
+
+    fn cards(hand: &str) -> Vec<Card> {
+        parse_cards(hand, hand).expect("expected valid cards")
+    }
+
+    #[test]
+    fn standard_and_joker_rules_work_for_example() {
+        // given
+        let hands = parse(HANDS).expect("expected successful parsing");
+
+        // then
+        assert_eq!(
+            total_winnings_with(&hands, &Rules::standard()),
+            total_winnings(hands.clone())
+        );
+        assert_eq!(total_winnings_with(&hands, &Rules::joker()), 5905);
+    }
+
+    #[test]
+    fn rules_agree_with_hand_types() {
+        // given
+        let joker = Rules::joker();
+
+        // then
+        for hand in ["JJJJJ", "2345J", "22J33", "2J3J4", "AAKKQ", "KTJJT"] {
+            let hand = Hand(cards(hand).try_into().expect("expected five cards"));
+            assert_eq!(Rules::standard().hand_type(&hand.0), hand.hand_type());
+            assert_eq!(joker.hand_type(&hand.0), hand.hand_type_joker());
+        }
+    }
+
+    #[test]
+    fn configured_rules_work() {
+        // given
+        let config = "order: J\nwildcards: J\nhand size: 4\n\
+            categories: high pair straight all-of:TJQKA three four\n";
+
+        // when
+        let rules = Rules::from_config(config).expect("expected a valid config");
+
+        // then
+        let category = |hand: &str| rules.strength(&cards(hand)).map(|s| s.category);
+        assert_eq!(category("2357"), Some(1));
+        assert_eq!(category("2245"), Some(2));
+        assert_eq!(category("2J45"), Some(3));
+        assert_eq!(category("QKJT"), Some(4));
+        assert_eq!(category("QQJK"), Some(5));
+        assert_eq!(category("JJJJ"), Some(6));
+        assert_eq!(category("22345"), None);
+    }
+
+    #[test]
+    fn from_config_reports_position_of_errors() {
+        // when
+        let error = Rules::from_config("wildcards: J\ncategories: pair flush")
+            .expect_err("expected an invalid config");
+
+        // then
+        assert_eq!((error.line, error.column), (2, 18));
+        assert_eq!(error.kind, ErrorKind::Unknown("category"));
+    }
+}
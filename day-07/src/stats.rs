@@ -0,0 +1,182 @@
+use crate::{card_from_byte, Card, HandType, Rules, CARDS, HAND_TYPES};
+use common::ParseError;
+
+/// Parses a partially known hand like `KK?7?`, where each `?` is an unknown card.
+pub fn parse_partial(hand: &str) -> Result<Vec<Option<Card>>, ParseError> {
+    hand.bytes()
+        .enumerate()
+        .map(|(i, c)| match c {
+            b'?' => Ok(None),
+            _ => card_from_byte(c)
+                .map(Some)
+                .map_err(|kind| ParseError::at(hand, hand.get(i..i + 1).unwrap_or(hand), kind)),
+        })
+        .collect()
+}
+
+/// Every hand the partially known cards can be, with any card for each unknown one. There are
+/// 13^n of them for n unknown cards.
+pub fn completions(partial: &[Option<Card>]) -> impl Iterator<Item = Vec<Card>> + '_ {
+    let unknown: Vec<usize> = (0..partial.len())
+        .filter(|i| partial[*i].is_none())
+        .collect();
+    let mut next: Option<Vec<Card>> = Some(
+        partial
+            .iter()
+            .map(|card| card.unwrap_or(Card::Two))
+            .collect(),
+    );
+    std::iter::from_fn(move || {
+        let current = next.take()?;
+        // count up like an odometer, the last unknown card changes fastest
+        let mut following = current.clone();
+        for i in unknown.iter().rev() {
+            if following[*i] == Card::A {
+                following[*i] = Card::Two;
+            } else {
+                following[*i] = CARDS[following[*i] as usize + 1];
+                next = Some(following);
+                break;
+            }
+        }
+        Some(current)
+    })
+}
+
+/// How the completions of a partially known hand are distributed over the hand types.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Statistics {
+    /// The number of completions.
+    pub completions: u64,
+    // the number of completions of each type, indexed by `HandType as usize`
+    counts: [u64; 7],
+    /// The strongest completion.
+    pub best: Vec<Card>,
+    /// The type of the strongest completion.
+    pub best_type: HandType,
+}
+
+impl Statistics {
+    /// The number of completions of the type.
+    pub fn count(&self, hand_type: HandType) -> u64 {
+        self.counts[hand_type as usize]
+    }
+
+    /// The share of completions of the type, between 0 and 1.
+    pub fn share(&self, hand_type: HandType) -> f64 {
+        self.count(hand_type) as f64 / self.completions as f64
+    }
+
+    /// The number of completions of each type, from the weakest type to the strongest.
+    pub fn distribution(&self) -> impl Iterator<Item = (HandType, u64)> + '_ {
+        HAND_TYPES
+            .iter()
+            .map(|hand_type| (*hand_type, self.count(*hand_type)))
+    }
+}
+
+/// Enumerates all completions of a partially known hand with the rules, `None` if the hand has
+/// the wrong number of cards for the rules.
+pub fn statistics(partial: &[Option<Card>], rules: &Rules) -> Option<Statistics> {
+    let mut counts = [0u64; 7];
+    let mut completions_count = 0;
+    let mut best = None;
+    for hand in completions(partial) {
+        let strength = rules.strength(&hand)?;
+        counts[rules.hand_type(&hand) as usize] += 1;
+        completions_count += 1;
+        if best.as_ref().is_none_or(|(best, _)| strength > *best) {
+            best = Some((strength, hand));
+        }
+    }
+    let (_, best) = best?;
+    Some(Statistics {
+        completions: completions_count,
+        counts,
+        best_type: rules.hand_type(&best),
+        best,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn partial(hand: &str) -> Vec<Option<Card>> {
+        parse_partial(hand).expect("expected successful parsing")
+    }
+
+    fn cards(hand: &str) -> Vec<Card> {
+        partial(hand).into_iter().flatten().collect()
+    }
+
+    #[test]
+    fn completions_works() {
+        // given
+        let hand = partial("2?4?6");
+
+        // when
+        let hands: Vec<Vec<Card>> = completions(&hand).collect();
+
+        // then
+        assert_eq!(hands.len(), 169);
+        assert_eq!(hands[0], cards("22426"));
+        assert_eq!(hands[1], cards("22436"));
+        assert_eq!(hands[168], cards("2A4A6"));
+        assert_eq!(completions(&partial("KTJJT")).count(), 1);
+    }
+
+    #[test]
+    fn statistics_works_with_standard_and_joker_rules() {
+        // given
+        let hand = partial("2345?");
+
+        // when
+        let standard = statistics(&hand, &Rules::standard()).expect("expected five cards");
+        let joker = statistics(&hand, &Rules::joker()).expect("expected five cards");
+
+        // then
+        assert_eq!(standard.completions, 13);
+        assert_eq!(standard.count(HandType::Pair), 4);
+        assert_eq!(standard.count(HandType::High), 9);
+        assert_eq!(standard.best, cards("23455"));
+        assert_eq!(joker.count(HandType::Pair), 5);
+        assert_eq!(joker.count(HandType::High), 8);
+        assert_eq!(joker.best, cards("23455"));
+        assert_eq!(joker.best_type, HandType::Pair);
+    }
+
+    #[test]
+    fn statistics_prefers_jokers_for_better_types() {
+        // given
+        let hand = partial("AAAA?");
+
+        // when
+        let standard = statistics(&hand, &Rules::standard()).expect("expected five cards");
+        let joker = statistics(&hand, &Rules::joker()).expect("expected five cards");
+
+        // then
+        assert_eq!(standard.count(HandType::Five), 1);
+        assert_eq!(joker.count(HandType::Five), 2);
+        assert_eq!(joker.count(HandType::Four), 11);
+        assert_eq!(joker.best, cards("AAAAA"));
+        assert_eq!(statistics(&partial("AAAA"), &Rules::joker()), None);
+    }
+
+    #[test]
+    fn statistics_counts_all_hands() {
+        // when
+        let stats = statistics(&partial("?????"), &Rules::standard()).expect("expected five cards");
+
+        // then
+        assert_eq!(stats.completions, 13u64.pow(5));
+        assert_eq!(
+            stats.distribution().map(|(_, count)| count).sum::<u64>(),
+            stats.completions
+        );
+        assert_eq!(stats.count(HandType::Five), 13);
+        assert_eq!(stats.count(HandType::Four), 13 * 12 * 5);
+        assert_eq!(stats.count(HandType::FullHouse), 13 * 12 * 10);
+        assert_eq!(stats.best, cards("AAAAA"));
+    }
+}
@@ -0,0 +1,321 @@
+use crate::{find_cycle, Cycle, Dir, Node};
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Write;
+
+/// The structure of a network, to see why the ghosts meet when they do.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Analysis<'a> {
+    /// The strongly connected components, each sorted by name, ordered by their first node.
+    pub components: Vec<Vec<&'a str>>,
+    /// The nodes that can not be reached from any node ending with `A`.
+    pub unreachable: Vec<&'a str>,
+    /// The nodes from which no node ending with `Z` can be reached.
+    pub dead_ends: Vec<&'a str>,
+    /// The cycle of the ghost starting at each node ending with `A`.
+    pub cycles: Vec<(&'a str, Cycle)>,
+}
+
+impl Cycle {
+    /// Whether the ghost is at a node ending with `Z` exactly after each multiple of the period,
+    /// which is what taking the least common multiple of the first ends relies on.
+    pub fn fits_lcm_shortcut(&self) -> bool {
+        self.prefix_ends.is_empty() && self.cycle_ends == [self.period]
+    }
+}
+
+// the node names sorted, and the indices of the left and right neighbours of each node
+fn index_nodes<'a>(nodes: &HashMap<&'a str, Node<'a>>) -> (Vec<&'a str>, Vec<[usize; 2]>) {
+    let mut names: Vec<&str> = nodes.keys().copied().collect();
+    names.sort_unstable();
+    let index: HashMap<&str, usize> = names.iter().enumerate().map(|(i, n)| (*n, i)).collect();
+    let edges = names
+        .iter()
+        .map(|name| {
+            let node = &nodes[name];
+            [index[node.left], index[node.right]]
+        })
+        .collect();
+    (names, edges)
+}
+
+// Tarjan's algorithm, without recursion because the networks are large
+fn components(edges: &[[usize; 2]]) -> Vec<Vec<usize>> {
+    const UNVISITED: usize = usize::MAX;
+    let mut order: Vec<usize> = vec![UNVISITED; edges.len()];
+    let mut low: Vec<usize> = vec![UNVISITED; edges.len()];
+    let mut on_stack: Vec<bool> = vec![false; edges.len()];
+    let mut stack: Vec<usize> = Vec::with_capacity(edges.len());
+    let mut components: Vec<Vec<usize>> = Vec::with_capacity(edges.len());
+    let mut counter = 0;
+    for root in 0..edges.len() {
+        if order[root] != UNVISITED {
+            continue;
+        }
+        // each node on the work stack with the index of the next edge to follow
+        let mut work: Vec<(usize, usize)> = vec![(root, 0)];
+        order[root] = counter;
+        low[root] = counter;
+        counter += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        while let Some((v, next)) = work.pop() {
+            if next < edges[v].len() {
+                work.push((v, next + 1));
+                let w = edges[v][next];
+                if order[w] == UNVISITED {
+                    order[w] = counter;
+                    low[w] = counter;
+                    counter += 1;
+                    stack.push(w);
+                    on_stack[w] = true;
+                    work.push((w, 0));
+                } else if on_stack[w] {
+                    low[v] = low[v].min(order[w]);
+                }
+            } else {
+                if low[v] == order[v] {
+                    let mut component: Vec<usize> = Vec::with_capacity(1);
+                    while let Some(w) = stack.pop() {
+                        on_stack[w] = false;
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    component.sort_unstable();
+                    components.push(component);
+                }
+                if let Some((parent, _)) = work.last() {
+                    low[*parent] = low[*parent].min(low[v]);
+                }
+            }
+        }
+    }
+    components.sort_unstable();
+    components
+}
+
+// all nodes that can be reached from the given ones, following the edges
+fn reachable<E: AsRef<[usize]>>(edges: &[E], from: impl Iterator<Item = usize>) -> Vec<bool> {
+    let mut reached: Vec<bool> = vec![false; edges.len()];
+    let mut queue: Vec<usize> = from.collect();
+    for node in &queue {
+        reached[*node] = true;
+    }
+    while let Some(node) = queue.pop() {
+        for next in edges[node].as_ref() {
+            if !reached[*next] {
+                reached[*next] = true;
+                queue.push(*next);
+            }
+        }
+    }
+    reached
+}
+
+/// Analyses the network and the paths of the ghosts starting at all nodes ending with `A`.
+pub fn analyse<'a>(
+    dirs: &[Dir],
+    nodes: &HashMap<&'a str, Node<'a>>,
+) -> Result<Analysis<'a>, String> {
+    let (names, edges) = index_nodes(nodes);
+    let starts = (0..names.len()).filter(|i| names[*i].ends_with('A'));
+    let ends = (0..names.len()).filter(|i| names[*i].ends_with('Z'));
+
+    let mut backward: Vec<Vec<usize>> = vec![Vec::with_capacity(2); names.len()];
+    for (from, to) in edges.iter().enumerate() {
+        for next in to {
+            backward[*next].push(from);
+        }
+    }
+    let from_starts = reachable(&edges, starts.clone());
+    let to_ends = reachable(&backward, ends);
+    let filter_names = |reached: &[bool]| -> Vec<&'a str> {
+        (0..names.len())
+            .filter(|i| !reached[*i])
+            .map(|i| names[i])
+            .collect()
+    };
+
+    Ok(Analysis {
+        components: components(&edges)
+            .into_iter()
+            .map(|component| component.into_iter().map(|i| names[i]).collect())
+            .collect(),
+        unreachable: filter_names(&from_starts),
+        dead_ends: filter_names(&to_ends),
+        cycles: starts
+            .map(|i| Ok((names[i], find_cycle(dirs, nodes, names[i])?)))
+            .collect::<Result<Vec<(&str, Cycle)>, String>>()?,
+    })
+}
+
+fn write_names(f: &mut fmt::Formatter<'_>, names: &[&str]) -> Result<(), fmt::Error> {
+    if names.is_empty() {
+        writeln!(f, " none")
+    } else {
+        writeln!(f, " {}", names.join(", "))
+    }
+}
+
+impl fmt::Display for Analysis<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        let nodes: usize = self.components.iter().map(|c| c.len()).sum();
+        writeln!(
+            f,
+            "{nodes} nodes in {} strongly connected components",
+            self.components.len()
+        )?;
+        for component in self.components.iter().filter(|c| c.len() > 1) {
+            writeln!(
+                f,
+                "  component of {}: {}",
+                component.len(),
+                component.join(", ")
+            )?;
+        }
+        write!(f, "unreachable from start nodes:")?;
+        write_names(f, &self.unreachable)?;
+        write!(f, "dead ends:")?;
+        write_names(f, &self.dead_ends)?;
+        for (start, cycle) in &self.cycles {
+            writeln!(
+                f,
+                "{start}: cycle of {} steps after {} steps, ends after {:?} and {:?} + k * {}{}",
+                cycle.period,
+                cycle.offset,
+                cycle.prefix_ends,
+                cycle.cycle_ends,
+                cycle.period,
+                if cycle.fits_lcm_shortcut() {
+                    ""
+                } else {
+                    " (does not fit the LCM shortcut)"
+                }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The network in the DOT language of Graphviz, with start nodes in green and end nodes in red.
+pub fn to_dot(nodes: &HashMap<&str, Node>) -> String {
+    let mut names: Vec<&str> = nodes.keys().copied().collect();
+    names.sort_unstable();
+    let mut dot = String::with_capacity(names.len() * 64);
+    dot.push_str("digraph network {\n  node [style=filled, fillcolor=white];\n");
+    for name in &names {
+        let color = if name.ends_with('A') {
+            "palegreen"
+        } else if name.ends_with('Z') {
+            "lightcoral"
+        } else {
+            continue;
+        };
+        // writing to a string does not fail
+        let _ = writeln!(dot, "  \"{name}\" [fillcolor={color}];");
+    }
+    for name in &names {
+        let node = &nodes[name];
+        if node.left == node.right {
+            let _ = writeln!(dot, "  \"{name}\" -> \"{}\" [label=\"LR\"];", node.left);
+        } else {
+            let _ = writeln!(dot, "  \"{name}\" -> \"{}\" [label=\"L\"];", node.left);
+            let _ = writeln!(dot, "  \"{name}\" -> \"{}\" [label=\"R\"];", node.right);
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse;
+
+    const EXAMPLE_1: &str = r#"RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, DDD)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)
+"#;
+
+    const EXAMPLE_3: &str = r#"LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+"#;
+
+    #[test]
+    fn analyse_finds_unreachable_nodes_and_dead_ends() {
+        // given
+        let (dirs, nodes) = parse(EXAMPLE_1).expect("expected successful parsing");
+
+        // when
+        let analysis = analyse(&dirs, &nodes).expect("expected successful analysis");
+
+        // then
+        assert_eq!(analysis.components.len(), 7);
+        assert_eq!(analysis.unreachable, vec!["GGG"]);
+        assert_eq!(analysis.dead_ends, vec!["BBB", "DDD", "EEE", "GGG"]);
+        assert_eq!(analysis.cycles.len(), 1);
+        assert!(!analysis.cycles[0].1.fits_lcm_shortcut());
+    }
+
+    #[test]
+    fn analyse_finds_components_and_cycles() {
+        // given
+        let (dirs, nodes) = parse(EXAMPLE_3).expect("expected successful parsing");
+
+        // when
+        let analysis = analyse(&dirs, &nodes).expect("expected successful analysis");
+
+        // then
+        assert_eq!(
+            analysis.components,
+            vec![
+                vec!["11A"],
+                vec!["11B", "11Z"],
+                vec!["22A"],
+                vec!["22B", "22C", "22Z"],
+                vec!["XXX"],
+            ]
+        );
+        assert_eq!(analysis.unreachable, Vec::<&str>::new());
+        assert_eq!(analysis.dead_ends, vec!["XXX"]);
+        let cycles: Vec<(&str, u64, u64, bool)> = analysis
+            .cycles
+            .iter()
+            .map(|(start, c)| (*start, c.offset, c.period, c.fits_lcm_shortcut()))
+            .collect();
+        assert_eq!(cycles, vec![("11A", 1, 2, true), ("22A", 1, 6, false)]);
+    }
+
+    #[test]
+    fn to_dot_colours_start_and_end_nodes() {
+        // given
+        let (_, nodes) = parse(EXAMPLE_3).expect("expected successful parsing");
+
+        // when
+        let dot = to_dot(&nodes);
+
+        // then
+        assert!(dot.starts_with("digraph network {\n"));
+        assert!(dot.contains("  \"11A\" [fillcolor=palegreen];\n"));
+        assert!(dot.contains("  \"22Z\" [fillcolor=lightcoral];\n"));
+        assert!(dot.contains("  \"11B\" -> \"11Z\" [label=\"R\"];\n"));
+        assert!(dot.contains("  \"22B\" -> \"22C\" [label=\"LR\"];\n"));
+        assert_eq!(dot.lines().count(), 2 + 4 + 12 + 1);
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{Dir, Node};
+use std::collections::HashMap;
+use std::fmt;
+
+/// The path of a ghost: after `offset` steps, it repeats every `period` steps, because it is at
+/// the same node at the same position in the directions again.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Cycle {
+    pub offset: u64,
+    pub period: u64,
+    /// The steps before the cycle after which the ghost is at a node ending with `Z`.
+    pub prefix_ends: Vec<u64>,
+    /// The steps of the first pass through the cycle after which the ghost is at a node ending
+    /// with `Z`. It is there again after each further period.
+    pub cycle_ends: Vec<u64>,
+}
+
+impl Cycle {
+    /// Whether the ghost is at a node ending with `Z` after the given number of steps.
+    pub fn is_end(&self, steps: u128) -> bool {
+        if steps < self.offset as u128 {
+            self.prefix_ends.contains(&(steps as u64))
+        } else {
+            let in_cycle = (steps - self.offset as u128) % self.period as u128;
+            self.cycle_ends.contains(&(self.offset + in_cycle as u64))
+        }
+    }
+}
+
+/// Follows the directions from the start node until the ghost is at a node at the same position
+/// in the directions for the second time.
+pub fn find_cycle(dirs: &[Dir], nodes: &HashMap<&str, Node>, start: &str) -> Result<Cycle, String> {
+    let mut seen: HashMap<(&str, usize), u64> = HashMap::with_capacity(nodes.len());
+    let mut ends: Vec<u64> = Vec::with_capacity(8);
+    let mut current: &str = start;
+    let mut steps: u64 = 0;
+    loop {
+        let dir_index = steps as usize % dirs.len();
+        if let Some(offset) = seen.insert((current, dir_index), steps) {
+            let (prefix_ends, cycle_ends) = ends.iter().partition(|end| **end < offset);
+            return Ok(Cycle {
+                offset,
+                period: steps - offset,
+                prefix_ends,
+                cycle_ends,
+            });
+        }
+        if current.ends_with('Z') {
+            ends.push(steps);
+        }
+        let node = nodes
+            .get(current)
+            .ok_or_else(|| format!("unable to find directions for node '{current}'"))?;
+        current = match dirs[dir_index] {
+            Dir::Left => node.left,
+            Dir::Right => node.right,
+        };
+        steps += 1;
+    }
+}
+
+/// When the ghosts are all at nodes ending with `Z` for the first time.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Synchronisation {
+    After(u128),
+    Never,
+}
+
+impl fmt::Display for Synchronisation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Synchronisation::After(steps) => write!(f, "after {steps} steps"),
+            Synchronisation::Never => write!(f, "never"),
+        }
+    }
+}
+
+/// The first number of steps after which every ghost is at a node ending with `Z`.
+pub fn synchronise(cycles: &[Cycle]) -> Result<Synchronisation, String> {
+    // before the cycle of a ghost starts, only its prefix ends are candidates
+    let before_cycles = cycles
+        .iter()
+        .flat_map(|cycle| cycle.prefix_ends.iter())
+        .map(|end| *end as u128)
+        .filter(|end| cycles.iter().all(|cycle| cycle.is_end(*end)))
+        .min();
+
+    // once all ghosts are in their cycles, the steps must solve a congruence for each ghost
+    let start = cycles.iter().map(|cycle| cycle.offset).max().unwrap_or(0) as u128;
+    let mut modulus: u128 = 1;
+    let mut residues: Vec<u128> = vec![0];
+    for cycle in cycles {
+        let period = cycle.period as u128;
+        let mut combined: Vec<u128> = Vec::with_capacity(residues.len() * cycle.cycle_ends.len());
+        let mut combined_modulus = modulus;
+        for residue in &residues {
+            for end in &cycle.cycle_ends {
+                if let Some((r, m)) = crt(*residue, modulus, *end as u128 % period, period)? {
+                    combined.push(r);
+                    combined_modulus = m;
+                }
+            }
+        }
+        combined.sort_unstable();
+        combined.dedup();
+        residues = combined;
+        modulus = combined_modulus;
+    }
+    let in_cycles = residues
+        .iter()
+        .map(|residue| {
+            if *residue >= start {
+                Ok(*residue)
+            } else {
+                (start - residue)
+                    .div_ceil(modulus)
+                    .checked_mul(modulus)
+                    .and_then(|n| n.checked_add(*residue))
+                    .ok_or_else(|| "the number of steps is too large".to_owned())
+            }
+        })
+        .collect::<Result<Vec<u128>, String>>()?
+        .into_iter()
+        .min();
+
+    Ok(match before_cycles.into_iter().chain(in_cycles).min() {
+        Some(steps) => Synchronisation::After(steps),
+        None => Synchronisation::Never,
+    })
+}
+
+// Solves x ≡ a (mod m), x ≡ b (mod n) for moduli that need not be coprime. The solution is
+// x ≡ r (mod lcm(m, n)), if there is one.
+fn crt(a: u128, m: u128, b: u128, n: u128) -> Result<Option<(u128, u128)>, String> {
+    let too_large = || "the cycles of the ghosts are too long".to_owned();
+    let (g, inverse) = gcd_inverse(m % n, n);
+    let diff = (b as i128 - (a % n) as i128).rem_euclid(n as i128) as u128;
+    if !diff.is_multiple_of(g) {
+        return Ok(None);
+    }
+    let reduced = n / g;
+    // m * k ≡ b - a (mod n), so k ≡ (b - a) / g * (m / g)^-1 (mod n / g)
+    let k = (diff / g % reduced)
+        .checked_mul(inverse % reduced)
+        .ok_or_else(too_large)?
+        % reduced;
+    let lcm = m.checked_mul(reduced).ok_or_else(too_large)?;
+    let x = m
+        .checked_mul(k)
+        .and_then(|mk| mk.checked_add(a))
+        .ok_or_else(too_large)?
+        % lcm;
+    Ok(Some((x, lcm)))
+}
+
+// The greatest common divisor g of m and n, and an inverse of m / g modulo n / g.
+fn gcd_inverse(m: u128, n: u128) -> (u128, u128) {
+    // extended Euclid, the coefficients stay below n in magnitude
+    let (mut old_r, mut r) = (m as i128, n as i128);
+    let (mut old_s, mut s) = (1i128, 0i128);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+    }
+    let g = old_r as u128;
+    let reduced = (n / g) as i128;
+    (g, old_s.rem_euclid(reduced) as u128)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn crt_works_for_moduli_with_common_divisors() {
+        assert_eq!(crt(2, 3, 1, 2), Ok(Some((5, 6))));
+        assert_eq!(crt(3, 6, 1, 4), Ok(Some((9, 12))));
+        assert_eq!(crt(0, 6, 1, 4), Ok(None));
+        assert_eq!(crt(0, 1, 4, 7), Ok(Some((4, 7))));
+    }
+
+    const PREFIX: &str = r#"L
+
+1AA = (1BB, 1BB)
+1BB = (1ZZ, 1ZZ)
+1ZZ = (1CC, 1CC)
+1CC = (1BB, 1BB)
+2AA = (2ZZ, 2ZZ)
+2ZZ = (2AA, 2AA)
+"#;
+
+    #[test]
+    fn synchronise_works_for_cycles_not_starting_at_the_start() {
+        // given
+        let (dirs, nodes) = parse(PREFIX).expect("expected successful parsing");
+
+        // when
+        let first = find_cycle(&dirs, &nodes, "1AA");
+        let second = find_cycle(&dirs, &nodes, "2AA");
+
+        // then
+        let first = first.expect("expected a cycle");
+        let second = second.expect("expected a cycle");
+        assert_eq!(
+            first,
+            Cycle {
+                offset: 1,
+                period: 3,
+                prefix_ends: vec![],
+                cycle_ends: vec![2],
+            }
+        );
+        assert_eq!((second.offset, second.period), (0, 2));
+        assert_eq!(synchronise(&[first, second]), Ok(Synchronisation::After(5)));
+    }
+
+    const NEVER: &str = r#"L
+
+1AA = (1ZZ, 1ZZ)
+1ZZ = (1AA, 1AA)
+2AA = (2BB, 2BB)
+2BB = (2ZZ, 2ZZ)
+2ZZ = (2BB, 2BB)
+3AA = (3ZZ, 3ZZ)
+3ZZ = (3BB, 3BB)
+3BB = (3BB, 3BB)
+"#;
+
+    #[test]
+    fn synchronise_reports_ghosts_that_never_meet() {
+        // given
+        let (dirs, nodes) = parse(NEVER).expect("expected successful parsing");
+        let cycle = |start| find_cycle(&dirs, &nodes, start).expect("expected a cycle");
+
+        // then
+        assert_eq!(
+            synchronise(&[cycle("1AA"), cycle("2AA")]),
+            Ok(Synchronisation::Never)
+        );
+        assert_eq!(
+            synchronise(&[cycle("1AA"), cycle("3AA")]),
+            Ok(Synchronisation::After(1))
+        );
+        assert_eq!(
+            synchronise(&[cycle("2AA"), cycle("3AA")]),
+            Ok(Synchronisation::Never)
+        );
+    }
+}
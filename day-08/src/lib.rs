@@ -0,0 +1,266 @@
+//! Day 8: Haunted Wasteland
+//!
+//! A network of nodes navigated with left/right instructions, by one camel or by a lot of ghosts.
+
+mod analysis;
+mod cycle;
+mod mode;
+
+pub use analysis::{analyse, to_dot, Analysis};
+pub use cycle::{find_cycle, synchronise, Cycle, Synchronisation};
+pub use mode::AnalyseMode;
+
+use common::{split_once, Answer, ErrorKind, ParseError, Solution};
+use std::collections::HashMap;
+
+/// Solves both parts of the puzzle, see [`Solution`].
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 8;
+
+    type Input<'a> = (Vec<Dir>, HashMap<&'a str, Node<'a>>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Answer {
+        let (dirs, nodes) = input;
+        find_path_length(dirs, nodes).into()
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Answer {
+        let (dirs, nodes) = input;
+        find_ghost_path_length(dirs, nodes).into()
+    }
+}
+
+/// The direction to go at a node.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Dir {
+    Left,
+    Right,
+}
+
+/// A node with the name of the node it is, and the nodes to its left and right.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Node<'a> {
+    pub from: &'a str,
+    pub left: &'a str,
+    pub right: &'a str,
+}
+
+fn parse_node(line: &str) -> Result<Node<'_>, ParseError> {
+    let (from, to) = split_once(line, line, " = ", "' = ' between node and its neighbours")?;
+    let (left, right) = to
+        .strip_prefix('(')
+        .and_then(|t| t.strip_suffix(')'))
+        .and_then(|t| t.split_once(", "))
+        .ok_or_else(|| {
+            ParseError::at(
+                line,
+                to,
+                ErrorKind::Missing("neighbours in the format '(L, R)'"),
+            )
+        })?;
+
+    Ok(Node { from, left, right })
+}
+
+/// Parses the directions and the nodes by name. All nodes that can be reached are known.
+pub fn parse(input: &str) -> Result<(Vec<Dir>, HashMap<&str, Node<'_>>), ParseError> {
+    let mut lines = input.lines();
+    let dirs = lines
+        .next()
+        .filter(|dirs| !dirs.is_empty())
+        .ok_or_else(|| ParseError::at(input, &input[..0], ErrorKind::Missing("directions")))?;
+
+    let dirs: Vec<Dir> = dirs
+        .char_indices()
+        .map(|(i, c)| match c {
+            'L' => Ok(Dir::Left),
+            'R' => Ok(Dir::Right),
+            _ => Err(ParseError::at(
+                dirs,
+                &dirs[i..i + c.len_utf8()],
+                ErrorKind::Unknown("direction"),
+            )),
+        })
+        .collect::<Result<Vec<Dir>, ParseError>>()?;
+
+    match lines.next() {
+        Some("") => {}
+        second => {
+            let second = second.unwrap_or(&input[input.len()..]);
+            let kind = ErrorKind::Missing("blank line between directions and nodes");
+            return Err(ParseError::at(input, second, kind));
+        }
+    }
+
+    let nodes = lines
+        .enumerate()
+        .map(|(i, line)| {
+            let node = parse_node(line).map_err(|e| e.on_line(i + 3))?;
+            Ok((node.from, node))
+        })
+        .collect::<Result<HashMap<&str, Node>, ParseError>>()?;
+
+    // every node that can be reached needs directions
+    for node in nodes.values() {
+        for neighbour in [node.left, node.right] {
+            if !nodes.contains_key(neighbour) {
+                return Err(ParseError::at(input, neighbour, ErrorKind::Unknown("node")));
+            }
+        }
+    }
+
+    Ok((dirs, nodes))
+}
+
+/// The number of steps from `AAA` to `ZZZ`, following the directions.
+pub fn find_path_length(dirs: &[Dir], nodes: &HashMap<&str, Node>) -> Result<usize, String> {
+    let mut steps: usize = 0;
+    let mut current_node: &str = "AAA";
+    while current_node != "ZZZ" {
+        let node = nodes
+            .get(current_node)
+            .ok_or_else(|| format!("unable to find directions for node '{current_node}'"))?;
+        current_node = match dirs[steps % dirs.len()] {
+            Dir::Left => node.left,
+            Dir::Right => node.right,
+        };
+        steps += 1
+    }
+    Ok(steps)
+}
+
+/// The number of steps from `start_node` to the first node ending with `Z`.
+pub fn find_any_end_path_length(
+    dirs: &[Dir],
+    nodes: &HashMap<&str, Node>,
+    start_node: &str,
+) -> Result<u64, String> {
+    let mut steps: u64 = 0;
+    let mut current_node: &str = start_node;
+    while !current_node.ends_with('Z') {
+        let node = nodes
+            .get(current_node)
+            .ok_or_else(|| format!("unable to find directions for node '{current_node}'"))?;
+        current_node = match dirs[steps as usize % dirs.len()] {
+            Dir::Left => node.left,
+            Dir::Right => node.right,
+        };
+        steps += 1
+    }
+    Ok(steps)
+}
+
+/// The number of steps until ghosts starting at all nodes ending with `A` are at nodes ending
+/// with `Z` at the same time, see [`synchronise`]. Fails if that never happens.
+pub fn find_ghost_path_length(dirs: &[Dir], nodes: &HashMap<&str, Node>) -> Result<u128, String> {
+    let cycles: Vec<Cycle> = nodes
+        .keys()
+        .filter(|node| node.ends_with('A'))
+        .map(|node| find_cycle(dirs, nodes, node))
+        .collect::<Result<Vec<Cycle>, String>>()?;
+    if cycles.is_empty() {
+        return Err("no starting nodes found".to_string());
+    }
+    match synchronise(&cycles)? {
+        Synchronisation::After(steps) => Ok(steps),
+        Synchronisation::Never => {
+            Err("the ghosts are never at nodes ending with 'Z' at the same time".to_string())
+        }
+    }
+}
+
+/// The greatest common divisor.
+pub fn gcd(mut m: u64, mut n: u64) -> u64 {
+    if m == 0 {
+        return n;
+    }
+    if n == 0 {
+        return m;
+    }
+    while n != 0 {
+        let h = m % n;
+        m = n;
+        n = h;
+    }
+    m
+}
+
+/// The least common multiple.
+pub fn lcm(m: u64, n: u64) -> u64 {
+    (m * n) / gcd(m, n)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE_1: &str = r#"RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, DDD)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)
+"#;
+
+    const EXAMPLE_2: &str = r#"LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+"#;
+
+    #[test]
+    fn find_path_length_works_for_example_1() {
+        // given
+        let (dirs, nodes) = parse(EXAMPLE_1).expect("expected successful parsing");
+
+        // when
+        let steps = find_path_length(&dirs, &nodes);
+
+        // then
+        assert_eq!(steps, Ok(2));
+    }
+
+    #[test]
+    fn find_path_length_works_for_example_2() {
+        // given
+        let (dirs, nodes) = parse(EXAMPLE_2).expect("expected successful parsing");
+
+        // when
+        let steps = find_path_length(&dirs, &nodes);
+
+        // then
+        assert_eq!(steps, Ok(6));
+    }
+    const EXAMPLE_3: &str = r#"LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+"#;
+    #[test]
+    fn find_ghost_path_length_works_for_example() {
+        // given
+        let (dirs, nodes) = parse(EXAMPLE_3).expect("expected successful parsing");
+
+        // when
+        let steps = find_ghost_path_length(&dirs, &nodes);
+
+        // then
+        assert_eq!(steps, Ok(6));
+    }
+}
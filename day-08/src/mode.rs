@@ -0,0 +1,44 @@
+use crate::{analyse, parse, to_dot};
+use common::{read_input, Mode};
+use std::path::Path;
+
+/// The `--analyse` and `--dot` modes of the binary: prints an analysis of each network, see
+/// [`analyse`], or the network as a Graphviz graph, see [`to_dot`].
+#[derive(Default)]
+pub struct AnalyseMode {
+    dot: bool,
+}
+
+impl Mode for AnalyseMode {
+    fn flags(&self) -> &[&str] {
+        &["--analyse", "--dot"]
+    }
+
+    fn usage(&self) -> &str {
+        "--analyse|--dot"
+    }
+
+    fn argument(
+        &mut self,
+        arg: &str,
+        _: &mut dyn Iterator<Item = &String>,
+    ) -> Result<bool, String> {
+        match arg {
+            "--analyse" => {}
+            "--dot" => self.dot = true,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    fn run(&mut self, input: &Path, name: &str) -> Result<(), String> {
+        let content = read_input(input)?;
+        let (dirs, nodes) = parse(&content).map_err(|e| e.to_string())?;
+        if self.dot {
+            print!("{}", to_dot(&nodes));
+        } else {
+            print!("{name}: {}", analyse(&dirs, &nodes)?);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+//! Day 9: Mirage Maintenance
+//!
+//! Extrapolation of value histories, both into the future and into the past, and the exact
+//! polynomials behind them.
+
+mod polynomial;
+
+pub use common::Rational;
+pub use polynomial::{FitError, Polynomial};
+
+use common::{parse_lines, parse_number, Answer, ParseError, Solution};
+
+/// Solves both parts of the puzzle, see [`Solution`].
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 9;
+
+    type Input<'a> = Vec<Vec<i64>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse_values(input)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Answer {
+        match prediction_sum(input) {
+            Some((_, pred_next)) => pred_next.into(),
+            None => Answer::unsolved("It's unpredicatable…"),
+        }
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Answer {
+        match prediction_sum(input) {
+            Some((pred_prev, _)) => pred_prev.into(),
+            None => Answer::unsolved("It's unpredicatable…"),
+        }
+    }
+}
+
+/// Parses a history of values like `0 3 6 9 12 15`.
+pub fn parse_line(line: &str) -> Result<Vec<i64>, ParseError> {
+    line.split_whitespace()
+        .map(|s| parse_number(line, s))
+        .collect()
+}
+
+/// Parses one history per line.
+pub fn parse_values(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    parse_lines(input, parse_line)
+}
+
+/// The values and their differences of each order, until the differences are all zero. If the
+/// values run out before that, the last row is empty.
+pub fn difference_table(values: &[i64]) -> Vec<Vec<i64>> {
+    let mut table: Vec<Vec<i64>> = Vec::with_capacity(values.len() + 1);
+    table.push(values.to_vec());
+    while table[table.len() - 1].iter().any(|v| *v != 0) {
+        let row = &table[table.len() - 1];
+        table.push(row.windows(2).map(|v| v[1] - v[0]).collect());
+    }
+    table
+}
+
+/// The previous and the next value of the history, or `None` if there are not enough values to
+/// tell.
+pub fn predict(values: &[i64]) -> Option<(i64, i64)> {
+    let table = difference_table(values);
+    let differences = &table[1..];
+
+    let mut first_diff: i64 = 0;
+    let mut last_diff: i64 = 0;
+    for diffs in differences.iter().rev() {
+        first_diff = diffs.first()? - first_diff;
+        last_diff += diffs.last()?;
+    }
+    let pred_prev = values.first().map(|v| v - first_diff)?;
+    let pred_next = values.last().map(|v| v + last_diff)?;
+
+    Some((pred_prev, pred_next))
+}
+
+/// The sums of the previous and next values of all histories.
+pub fn prediction_sum(values: &[Vec<i64>]) -> Option<(i64, i64)> {
+    values
+        .iter()
+        .map(|v| predict(v))
+        .try_fold((0, 0), |(sum_p, sum_n), next| {
+            let (p, n) = next?;
+            Some((sum_p + p, sum_n + n))
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = r#"0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45
+"#;
+
+    #[test]
+    fn predict_works_for_example() {
+        assert_eq!(predict(&[0, 3, 6, 9, 12, 15]), Some((-3, 18)));
+        assert_eq!(predict(&[1, 3, 6, 10, 15, 21]), Some((0, 28)));
+        assert_eq!(predict(&[10, 13, 16, 21, 30, 45]), Some((5, 68)));
+    }
+
+    #[test]
+    fn prediction_sum_works_for_example() {
+        // given
+        let values = parse_values(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let sum = prediction_sum(&values);
+
+        // then
+        assert_eq!(sum, Some((2, 114)));
+    }
+}
@@ -0,0 +1,252 @@
+use crate::{difference_table, Rational};
+use std::fmt;
+
+/// Why a history could not be fitted with a polynomial.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum FitError {
+    /// The differences of order `max_degree + 1` are not all zero, so no polynomial of at most
+    /// that degree produces the history.
+    NotPolynomial { max_degree: usize },
+    /// There are fewer values than the `needed` ones to tell whether the history is a polynomial
+    /// of at most the degree.
+    TooFewValues { needed: usize },
+    /// The coefficients are too large.
+    Overflow,
+}
+
+impl fmt::Display for FitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            FitError::NotPolynomial { max_degree } => {
+                write!(f, "not a polynomial of degree {max_degree} or less")
+            }
+            FitError::TooFewValues { needed } => {
+                write!(f, "at least {needed} values are needed")
+            }
+            FitError::Overflow => write!(f, "the coefficients are too large"),
+        }
+    }
+}
+
+/// The polynomial that produces the values of a history, with the index of the value (starting
+/// at 0) as argument.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Polynomial {
+    // the first value of each row of the difference table without trailing zeros, to evaluate at
+    // integer indices exactly
+    differences: Vec<i64>,
+    // the coefficients, from the constant one up, without trailing zeros
+    coefficients: Vec<Rational>,
+}
+
+impl Polynomial {
+    /// The polynomial of the lowest degree that produces all values. Its degree is one less than
+    /// the number of values, unless the differences become zero before.
+    pub fn interpolate(values: &[i64]) -> Result<Polynomial, FitError> {
+        if values.is_empty() {
+            return Err(FitError::TooFewValues { needed: 1 });
+        }
+        let mut differences: Vec<i64> = difference_table(values)
+            .iter()
+            .filter_map(|row| row.first().copied())
+            .collect();
+        while differences.last() == Some(&0) {
+            differences.pop();
+        }
+        let coefficients = to_coefficients(&differences).ok_or(FitError::Overflow)?;
+        Ok(Polynomial {
+            differences,
+            coefficients,
+        })
+    }
+
+    /// Like [`Polynomial::interpolate`], but only if the values are known to come from a polynomial
+    /// of at most the given degree, i.e. the differences of one order more are all zero.
+    pub fn fit(values: &[i64], max_degree: usize) -> Result<Polynomial, FitError> {
+        let table = difference_table(values);
+        let degree = match table.last() {
+            Some(row) if !row.is_empty() => table.len().checked_sub(2),
+            _ if values.len() > max_degree + 1 => {
+                return Err(FitError::NotPolynomial { max_degree })
+            }
+            _ => {
+                return Err(FitError::TooFewValues {
+                    needed: max_degree + 2,
+                })
+            }
+        };
+        if degree.is_some_and(|degree| degree > max_degree) {
+            return Err(FitError::NotPolynomial { max_degree });
+        }
+        Polynomial::interpolate(values)
+    }
+
+    /// The degree, `None` for the polynomial that is always 0.
+    pub fn degree(&self) -> Option<usize> {
+        self.coefficients.len().checked_sub(1)
+    }
+
+    /// The coefficients, from the constant one up to the one of the highest power.
+    pub fn coefficients(&self) -> &[Rational] {
+        &self.coefficients
+    }
+
+    /// The value at an index, also before 0 or far after the history, `None` if it is too large.
+    /// The value at an integer index is always an integer.
+    pub fn at(&self, index: i64) -> Option<i128> {
+        // Newton's forward differences: sum of the k-th differences times binomial(index, k)
+        let mut value: i128 = 0;
+        let mut binomial: i128 = 1;
+        for (k, difference) in self.differences.iter().enumerate() {
+            if k > 0 {
+                binomial = binomial.checked_mul(index as i128 - k as i128 + 1)? / k as i128;
+            }
+            value = value.checked_add(binomial.checked_mul(*difference as i128)?)?;
+        }
+        Some(value)
+    }
+
+    /// The value at any rational point, `None` if it is too large.
+    pub fn eval(&self, x: Rational) -> Option<Rational> {
+        self.coefficients
+            .iter()
+            .rev()
+            .try_fold(Rational::ZERO, |value, c| {
+                value.checked_mul(x)?.checked_add(*c)
+            })
+    }
+}
+
+// Converts the Newton form sum(d_k * x(x-1)…(x-k+1) / k!) into coefficients of powers of x.
+fn to_coefficients(differences: &[i64]) -> Option<Vec<Rational>> {
+    let mut coefficients: Vec<Rational> = vec![Rational::ZERO; differences.len()];
+    // the coefficients of x(x-1)…(x-k+1) / k!, starting with k = 0
+    let mut basis: Vec<Rational> = vec![Rational::ONE];
+    for (k, difference) in differences.iter().enumerate() {
+        if k > 0 {
+            // multiply with (x - k + 1) / k
+            let shift = Rational::integer(k as i128 - 1);
+            let divisor = Rational::integer(k as i128);
+            let mut next = vec![Rational::ZERO; basis.len() + 1];
+            for (power, c) in basis.iter().enumerate() {
+                next[power + 1] = next[power + 1].checked_add(c.checked_div(divisor)?)?;
+                next[power] =
+                    next[power].checked_sub(c.checked_mul(shift)?.checked_div(divisor)?)?;
+            }
+            basis = next;
+        }
+        let d = Rational::integer(*difference as i128);
+        for (power, c) in basis.iter().enumerate() {
+            coefficients[power] = coefficients[power].checked_add(c.checked_mul(d)?)?;
+        }
+    }
+    while coefficients.last().is_some_and(|c| c.is_zero()) {
+        coefficients.pop();
+    }
+    Some(coefficients)
+}
+
+impl fmt::Display for Polynomial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        if self.coefficients.is_empty() {
+            return write!(f, "0");
+        }
+        let terms = self
+            .coefficients
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, c)| !c.is_zero());
+        for (i, (power, c)) in terms.enumerate() {
+            let negative = c.reduce().numerator() < 0;
+            match (i, negative) {
+                (0, true) => write!(f, "-")?,
+                (0, false) => {}
+                (_, true) => write!(f, " - ")?,
+                (_, false) => write!(f, " + ")?,
+            }
+            let magnitude = if negative { -*c } else { *c };
+            if power == 0 || magnitude != Rational::ONE {
+                write!(f, "{magnitude}")?;
+            }
+            match power {
+                0 => {}
+                1 => write!(f, "x")?,
+                _ => write!(f, "x^{power}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn fit(values: &[i64]) -> Polynomial {
+        Polynomial::fit(values, values.len()).expect("expected a polynomial")
+    }
+
+    #[test]
+    fn fit_recovers_polynomials_of_example() {
+        // when
+        let linear = fit(&[0, 3, 6, 9, 12, 15]);
+        let quadratic = fit(&[1, 3, 6, 10, 15, 21]);
+        let cubic = fit(&[10, 13, 16, 21, 30, 45]);
+
+        // then
+        assert_eq!(linear.to_string(), "3x");
+        assert_eq!(quadratic.to_string(), "1/2x^2 + 3/2x + 1");
+        assert_eq!(cubic.to_string(), "1/3x^3 - x^2 + 11/3x + 10");
+        assert_eq!(cubic.degree(), Some(3));
+        assert_eq!((linear.at(-1), linear.at(6)), (Some(-3), Some(18)));
+        assert_eq!((quadratic.at(-1), quadratic.at(6)), (Some(0), Some(28)));
+        assert_eq!((cubic.at(-1), cubic.at(6)), (Some(5), Some(68)));
+    }
+
+    #[test]
+    fn at_works_far_from_the_history() {
+        // given
+        let cubic = fit(&[10, 13, 16, 21, 30, 45]);
+        let x: i128 = 1_000_000;
+
+        // then
+        let expected = (x * x * x - 3 * x * x + 11 * x) / 3 + 10;
+        assert_eq!(cubic.at(1_000_000), Some(expected));
+        assert_eq!(
+            cubic.eval(Rational::integer(x)),
+            Some(Rational::integer(expected))
+        );
+        assert_eq!(
+            cubic.at(-1_000_000),
+            Some(-(x * x * x + 3 * x * x + 11 * x) / 3 + 10)
+        );
+        assert_eq!(cubic.eval(Rational::new(1, 2)), Some(Rational::new(93, 8)));
+        assert_eq!(fit(&[7, 7, 7]).at(i64::MAX), Some(7));
+        assert_eq!(fit(&[0, 0]).degree(), None);
+    }
+
+    #[test]
+    fn fit_reports_histories_of_too_high_degree() {
+        // given
+        let cubic = [10, 13, 16, 21, 30, 45];
+
+        // then
+        assert_eq!(
+            Polynomial::fit(&cubic, 2),
+            Err(FitError::NotPolynomial { max_degree: 2 })
+        );
+        assert_eq!(
+            Polynomial::fit(&[1, 2, 4, 8, 16, 32], 3),
+            Err(FitError::NotPolynomial { max_degree: 3 })
+        );
+        assert_eq!(
+            Polynomial::fit(&[1, 2, 4, 8, 16, 32], 5),
+            Err(FitError::TooFewValues { needed: 7 })
+        );
+        assert_eq!(
+            Polynomial::interpolate(&[1, 2, 4, 8, 16, 32]).map(|p| p.at(6)),
+            Ok(Some(63))
+        );
+    }
+}
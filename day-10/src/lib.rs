@@ -0,0 +1,456 @@
+//! Day 10: Pipe Maze
+//!
+//! A loop of pipes, how far it reaches and how many tiles it encloses, counted by flooding the
+//! outside or with the shoelace formula.
+
+mod mode;
+mod render;
+mod shoelace;
+
+pub use mode::ExploreMode;
+pub use render::{classify, render, Tile};
+pub use shoelace::{enclosed_area_shoelace, enclosed_area_with, find_loop, AreaMethod};
+
+use common::{Answer, ErrorKind, ParseError, Solution};
+use grid::Grid;
+use std::collections::{HashMap, HashSet};
+
+/// Solves both parts of the puzzle, see [`Solution`].
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 10;
+
+    type Input<'a> = (Node, Edges);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Answer {
+        let (start, edges) = input;
+        match loop_distance(*start, edges) {
+            Some(distance) => distance.into(),
+            None => Answer::unsolved("I got lost in the tubes…"),
+        }
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Answer {
+        let (start, edges) = input;
+        match enclosed_area(*start, edges) {
+            Some(area) => area.into(),
+            None => Answer::unsolved("I got lost between the tubes…"),
+        }
+    }
+}
+
+/// The position of a tile as `(x, y)`.
+pub type Node = (usize, usize);
+/// The two tiles every pipe tile connects to.
+pub type Edges = HashMap<Node, (Node, Node)>;
+
+/// Parses the maze into the starting position and the connections of all pipes, including the
+/// start.
+pub fn parse(input: &str) -> Result<(Node, Edges), ParseError> {
+    let tiles: Grid<char> = Grid::parse(input, |c| match c {
+        'S' | '|' | '-' | 'L' | 'J' | '7' | 'F' | '.' => Ok(c),
+        _ => Err(ErrorKind::Unknown("tile")),
+    })?;
+
+    let mut edges: Edges = HashMap::with_capacity(tiles.len());
+    let mut start: Option<Node> = None;
+
+    for ((x, y), tile) in tiles.iter() {
+        match tile {
+            'S' => {
+                // assumption: there is only one start
+                start = Some((x, y));
+                let start_error = |kind| ParseError {
+                    line: y + 1,
+                    column: x + 1,
+                    text: "S".to_owned(),
+                    kind,
+                };
+                // assumption: just by looking at the neighbours, there are exactly two tiles
+                // that connect to S, so there is only one possible tile for S
+                let dirs = [
+                    connects_north(&tiles, x, y),
+                    connects_east(&tiles, x, y),
+                    connects_south(&tiles, x, y),
+                    connects_west(&tiles, x, y),
+                ];
+                let mut connections = dirs.iter().filter_map(|d| *d);
+                let edge = (
+                    connections
+                        .next()
+                        .ok_or_else(|| start_error(ErrorKind::Missing("connections")))?,
+                    connections
+                        .next()
+                        .ok_or_else(|| start_error(ErrorKind::Missing("second connection")))?,
+                );
+                if connections.next().is_some() {
+                    let kind = ErrorKind::Invalid("ambiguous connections".to_owned());
+                    return Err(start_error(kind));
+                }
+                edges.insert((x, y), edge);
+            }
+            '|' => {
+                if let Some(north) = connects_north(&tiles, x, y) {
+                    if let Some(south) = connects_south(&tiles, x, y) {
+                        edges.insert((x, y), (north, south));
+                    }
+                }
+            }
+            '-' => {
+                if let Some(east) = connects_east(&tiles, x, y) {
+                    if let Some(west) = connects_west(&tiles, x, y) {
+                        edges.insert((x, y), (east, west));
+                    }
+                }
+            }
+            'L' => {
+                if let Some(north) = connects_north(&tiles, x, y) {
+                    if let Some(east) = connects_east(&tiles, x, y) {
+                        edges.insert((x, y), (north, east));
+                    }
+                }
+            }
+            'J' => {
+                if let Some(north) = connects_north(&tiles, x, y) {
+                    if let Some(west) = connects_west(&tiles, x, y) {
+                        edges.insert((x, y), (north, west));
+                    }
+                }
+            }
+            '7' => {
+                if let Some(south) = connects_south(&tiles, x, y) {
+                    if let Some(west) = connects_west(&tiles, x, y) {
+                        edges.insert((x, y), (south, west));
+                    }
+                }
+            }
+            'F' => {
+                if let Some(south) = connects_south(&tiles, x, y) {
+                    if let Some(east) = connects_east(&tiles, x, y) {
+                        edges.insert((x, y), (south, east));
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    let start = start.ok_or_else(|| {
+        ParseError::at(input, &input[..0], ErrorKind::Missing("starting point 'S'"))
+    })?;
+    Ok((start, edges))
+}
+
+fn connects_north(tiles: &Grid<char>, x: usize, y: usize) -> Option<Node> {
+    if y == 0 {
+        None
+    } else if ['|', '7', 'F', 'S'].contains(tiles.get(x, y - 1)?) {
+        Some((x, y - 1))
+    } else {
+        None
+    }
+}
+
+fn connects_east(tiles: &Grid<char>, x: usize, y: usize) -> Option<Node> {
+    if ['-', '7', 'J', 'S'].contains(tiles.get(x + 1, y)?) {
+        Some((x + 1, y))
+    } else {
+        None
+    }
+}
+
+fn connects_south(tiles: &Grid<char>, x: usize, y: usize) -> Option<Node> {
+    if ['|', 'L', 'J', 'S'].contains(tiles.get(x, y + 1)?) {
+        Some((x, y + 1))
+    } else {
+        None
+    }
+}
+
+fn connects_west(tiles: &Grid<char>, x: usize, y: usize) -> Option<Node> {
+    if x == 0 {
+        None
+    } else if ['-', 'L', 'F', 'S'].contains(tiles.get(x - 1, y)?) {
+        Some((x - 1, y))
+    } else {
+        None
+    }
+}
+
+/// The number of steps to the point of the loop that is farthest from the start.
+pub fn loop_distance(start: Node, edges: &Edges) -> Option<usize> {
+    let (mut dir1, mut dir2) = edges.get(&start)?;
+    let mut steps = 1;
+    let (mut prev1, mut prev2) = (start, start);
+
+    while dir1 != dir2 {
+        steps += 1;
+        let (next1, next2) = edges.get(&dir1)?;
+        if prev1 == *next1 {
+            prev1 = dir1;
+            dir1 = *next2;
+        } else {
+            prev1 = dir1;
+            dir1 = *next1;
+        }
+        if dir1 == dir2 {
+            break;
+        }
+        let (next1, next2) = edges.get(&dir2)?;
+        if prev2 == *next1 {
+            prev2 = dir2;
+            dir2 = *next2;
+        } else {
+            prev2 = dir2;
+            dir2 = *next1;
+        }
+    }
+    Some(steps)
+}
+
+fn filter_loop_edges_in_double_space(start: Node, edges: &Edges) -> Option<HashSet<Node>> {
+    let (mut current, _) = edges.get(&start)?;
+    let mut loop_edges: HashSet<Node> = HashSet::with_capacity(edges.len() * 2);
+    let mut prev = start;
+    loop_edges.insert((2 + start.0 * 2, 2 + start.1 * 2));
+    loop_edges.insert(odd_in_between(start, current));
+    while current != start {
+        let (dir1, dir2) = edges.get(&current)?;
+        loop_edges.insert((2 + current.0 * 2, 2 + current.1 * 2));
+        if *dir1 == prev {
+            loop_edges.insert(odd_in_between(current, *dir2));
+            prev = current;
+            current = *dir2;
+        } else {
+            loop_edges.insert(odd_in_between(current, *dir1));
+            prev = current;
+            current = *dir1;
+        }
+    }
+    Some(loop_edges)
+}
+
+fn odd_in_between((ax, ay): Node, (bx, by): Node) -> Node {
+    // assumption: a and b are neighbours (so ax == bx or ay == by)
+    if ax == bx {
+        (ax * 2 + 2, ay.min(by) * 2 + 3)
+    } else {
+        (ax.min(bx) * 2 + 3, ay * 2 + 2)
+    }
+}
+
+/// The number of tiles enclosed by the loop.
+pub fn enclosed_area(start: Node, edges: &Edges) -> Option<usize> {
+    // idea: double all positions (to make space between pipes), then flood the outside area, then discard
+    // odd positions, then subtract it from the total area
+    let double_edges = filter_loop_edges_in_double_space(start, edges)?;
+
+    let width = double_edges.iter().map(|(x, _)| x).max()? + 2;
+    let height = double_edges.iter().map(|(_, y)| y).max()? + 2;
+
+    let mut visited: HashSet<Node> = HashSet::with_capacity(width * height);
+
+    let mut queue: Vec<Node> = Vec::with_capacity(width * height);
+    queue.push((0, 0));
+
+    while let Some((x, y)) = queue.pop() {
+        visited.insert((x, y));
+        if x != 0 && !double_edges.contains(&(x - 1, y)) && !visited.contains(&(x - 1, y)) {
+            queue.push((x - 1, y));
+        }
+        if y != 0 && !double_edges.contains(&(x, y - 1)) && !visited.contains(&(x, y - 1)) {
+            queue.push((x, y - 1));
+        }
+        if x + 1 < width && !double_edges.contains(&(x + 1, y)) && !visited.contains(&(x + 1, y)) {
+            queue.push((x + 1, y));
+        }
+        if y + 1 < height && !double_edges.contains(&(x, y + 1)) && !visited.contains(&(x, y + 1)) {
+            queue.push((x, y + 1));
+        }
+    }
+
+    Some(
+        (width / 2) * (height / 2)
+            - visited
+                .iter()
+                .filter(|(x, y)| x % 2 == 0 && y % 2 == 0)
+                .count()
+            - double_edges
+                .iter()
+                .filter(|(x, y)| x % 2 == 0 && y % 2 == 0)
+                .count(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = r#"..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...
+"#;
+
+    #[test]
+    fn loop_distances_works_for_example() {
+        // given
+        let (start, edges) = parse(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let result = loop_distance(start, &edges);
+
+        // then
+        assert_eq!(result, Some(8));
+    }
+
+    const ENCLOSED_EX1: &str = r#"..........
+.S------7.
+.|F----7|.
+.||....||.
+.||....||.
+.|L-7F-J|.
+.|..||..|.
+.L--JL--J.
+..........
+"#;
+
+    const ENCLOSED_EX2: &str = r#".F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...
+"#;
+
+    const ENCLOSED_EX3: &str = r#"FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L
+"#;
+
+    #[test]
+    fn enclosed_area_works_for_example1() {
+        // given
+        let (start, edges) = parse(ENCLOSED_EX1).expect("expected successful parsing");
+
+        // when
+        let area = enclosed_area(start, &edges);
+
+        assert_eq!(area, Some(4));
+    }
+
+    #[test]
+    fn enclosed_area_works_for_example2() {
+        // given
+        let (start, edges) = parse(ENCLOSED_EX2).expect("expected successful parsing");
+
+        // when
+        let area = enclosed_area(start, &edges);
+
+        assert_eq!(area, Some(8));
+    }
+
+    #[test]
+    fn enclosed_area_works_for_example3() {
+        // given
+        let (start, edges) = parse(ENCLOSED_EX3).expect("expected successful parsing");
+
+        // when
+        let area = enclosed_area(start, &edges);
+
+        assert_eq!(area, Some(10));
+    }
+
+    const EXAMPLES: [&str; 4] = [EXAMPLE, ENCLOSED_EX1, ENCLOSED_EX2, ENCLOSED_EX3];
+
+    #[test]
+    fn shoelace_agrees_with_flood_fill() {
+        for example in EXAMPLES {
+            // given
+            let (start, edges) = parse(example).expect("expected successful parsing");
+
+            // when
+            let flood = enclosed_area_with(start, &edges, AreaMethod::FloodFill);
+            let shoelace = enclosed_area_with(start, &edges, AreaMethod::Shoelace);
+
+            // then
+            assert_eq!(shoelace, flood);
+        }
+    }
+
+    #[test]
+    fn find_loop_is_twice_as_long_as_the_loop_distance() {
+        for example in EXAMPLES {
+            // given
+            let (start, edges) = parse(example).expect("expected successful parsing");
+
+            // when
+            let tiles = find_loop(start, &edges).expect("expected a loop");
+
+            // then
+            assert_eq!(Some(tiles.len() / 2), loop_distance(start, &edges));
+        }
+    }
+
+    #[test]
+    fn classify_agrees_with_flood_fill() {
+        for example in EXAMPLES {
+            // given
+            let (start, edges) = parse(example).expect("expected successful parsing");
+            let lines: Vec<&str> = example.lines().collect();
+
+            // when
+            let grid =
+                classify(start, &edges, lines[0].len(), lines.len()).expect("expected a loop");
+
+            // then
+            let inside = grid
+                .iter()
+                .filter(|(_, tile)| **tile == Tile::Inside)
+                .count();
+            assert_eq!(Some(inside), enclosed_area(start, &edges));
+        }
+    }
+
+    #[test]
+    fn render_works_for_example1() {
+        // given
+        let (start, edges) = parse(ENCLOSED_EX1).expect("expected successful parsing");
+
+        // when
+        let rendered = render(ENCLOSED_EX1, start, &edges);
+
+        // then
+        assert_eq!(
+            rendered.as_deref(),
+            Some(
+                r#"░░░░░░░░░░
+░┏━━━━━━┓░
+░┃┏━━━━┓┃░
+░┃┃░░░░┃┃░
+░┃┃░░░░┃┃░
+░┃┗━┓┏━┛┃░
+░┃▓▓┃┃▓▓┃░
+░┗━━┛┗━━┛░
+░░░░░░░░░░
+"#
+            )
+        );
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{enclosed_area_with, parse, render, AreaMethod};
+use common::{option_value, read_input, Mode};
+use std::path::Path;
+
+/// The `--area` and `--render` modes of the binary: prints the enclosed area of each maze with the
+/// chosen method, or the rendered maze, see [`render`]. Only one of them can be given.
+#[derive(Default)]
+pub struct ExploreMode {
+    method: Option<AreaMethod>,
+    render: bool,
+}
+
+impl Mode for ExploreMode {
+    fn flags(&self) -> &[&str] {
+        &["--area", "--render"]
+    }
+
+    fn usage(&self) -> &str {
+        "--area flood|shoelace|--render"
+    }
+
+    fn argument(
+        &mut self,
+        arg: &str,
+        args: &mut dyn Iterator<Item = &String>,
+    ) -> Result<bool, String> {
+        match arg {
+            "--render" => self.render = true,
+            "--area" => self.method = Some(option_value(args, arg)?.parse()?),
+            _ => return Ok(false),
+        }
+        if self.render && self.method.is_some() {
+            return Err("option '--area' can not be combined with '--render'".to_owned());
+        }
+        Ok(true)
+    }
+
+    fn run(&mut self, input: &Path, name: &str) -> Result<(), String> {
+        let content = read_input(input)?;
+        let (start, edges) = parse(&content).map_err(|e| e.to_string())?;
+        let output = match self.method {
+            Some(method) => {
+                enclosed_area_with(start, &edges, method).map(|area| format!("{name}: {area}\n"))
+            }
+            None => render(&content, start, &edges),
+        };
+        print!(
+            "{}",
+            output.ok_or_else(|| "unable to follow the loop".to_owned())?
+        );
+        Ok(())
+    }
+}
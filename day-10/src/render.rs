@@ -0,0 +1,85 @@
+use crate::{find_loop, Edges, Node};
+use grid::Grid;
+
+/// What a tile of the maze is, with respect to the loop.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Tile {
+    /// A pipe of the loop, with the directions it connects to (north, east, south, west).
+    Loop([bool; 4]),
+    /// A tile enclosed by the loop, whatever is on it.
+    Inside,
+    /// A tile outside of the loop, whatever is on it.
+    Outside,
+}
+
+impl Tile {
+    fn symbol(self) -> char {
+        match self {
+            Tile::Loop([true, false, true, false]) => '┃',
+            Tile::Loop([false, true, false, true]) => '━',
+            Tile::Loop([true, true, false, false]) => '┗',
+            Tile::Loop([true, false, false, true]) => '┛',
+            Tile::Loop([false, false, true, true]) => '┓',
+            Tile::Loop([false, true, true, false]) => '┏',
+            Tile::Loop(_) => '╋',
+            Tile::Inside => '▓',
+            Tile::Outside => '░',
+        }
+    }
+}
+
+// the directions (north, east, south, west) from a tile to its neighbours
+fn connections((x, y): Node, neighbours: [Node; 2]) -> [bool; 4] {
+    let mut dirs = [false; 4];
+    for (nx, ny) in neighbours {
+        if ny < y {
+            dirs[0] = true;
+        } else if nx > x {
+            dirs[1] = true;
+        } else if ny > y {
+            dirs[2] = true;
+        } else if nx < x {
+            dirs[3] = true;
+        }
+    }
+    dirs
+}
+
+/// Classifies every tile of a maze of the given size. A tile is inside if a line from it to the
+/// left edge crosses the loop an odd number of times, counting only pipes that lead north.
+pub fn classify(start: Node, edges: &Edges, width: usize, height: usize) -> Option<Grid<Tile>> {
+    let tiles = find_loop(start, edges)?;
+    let mut grid: Grid<Tile> = Grid::filled(width, height, Tile::Outside);
+    for (i, tile) in tiles.iter().enumerate() {
+        let prev = tiles[(i + tiles.len() - 1) % tiles.len()];
+        let next = tiles[(i + 1) % tiles.len()];
+        if !grid.set(tile.0, tile.1, Tile::Loop(connections(*tile, [prev, next]))) {
+            return None;
+        }
+    }
+    for y in 0..height {
+        let mut inside = false;
+        for x in 0..width {
+            match grid[(x, y)] {
+                Tile::Loop(dirs) => inside ^= dirs[0],
+                _ if inside => grid[(x, y)] = Tile::Inside,
+                _ => {}
+            }
+        }
+    }
+    Some(grid)
+}
+
+/// Redraws the maze with the loop in box-drawing characters, tiles inside of it as `▓` and
+/// tiles outside as `░`. Pipes that are not part of the loop are not drawn.
+pub fn render(maze: &str, start: Node, edges: &Edges) -> Option<String> {
+    let width = maze.lines().map(|line| line.chars().count()).max()?;
+    let height = maze.lines().count();
+    let grid = classify(start, edges, width, height)?;
+    let mut out = String::with_capacity((width * 3 + 1) * height);
+    for row in grid.rows() {
+        out.extend(row.iter().map(|tile| tile.symbol()));
+        out.push('\n');
+    }
+    Some(out)
+}
@@ -0,0 +1,69 @@
+use crate::{enclosed_area, Edges, Node};
+use std::str::FromStr;
+
+/// How to count the tiles enclosed by the loop.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Default)]
+pub enum AreaMethod {
+    /// Flood the outside of the loop with space between the tiles, see [`enclosed_area`].
+    #[default]
+    FloodFill,
+    /// The shoelace formula for the area of the loop, and Pick's theorem for the tiles in it.
+    Shoelace,
+}
+
+impl FromStr for AreaMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "flood" => Ok(AreaMethod::FloodFill),
+            "shoelace" => Ok(AreaMethod::Shoelace),
+            _ => Err(format!(
+                "unknown area method '{s}', expected 'flood' or 'shoelace'"
+            )),
+        }
+    }
+}
+
+/// The tiles of the loop in the order they are connected, beginning with the start.
+pub fn find_loop(start: Node, edges: &Edges) -> Option<Vec<Node>> {
+    let mut tiles: Vec<Node> = Vec::with_capacity(edges.len());
+    let (mut current, _) = edges.get(&start)?;
+    let mut prev = start;
+    tiles.push(start);
+    while current != start {
+        // a path that does not lead back to the start would visit the same tile again
+        if tiles.len() > edges.len() {
+            return None;
+        }
+        tiles.push(current);
+        let (dir1, dir2) = edges.get(&current)?;
+        let next = if *dir1 == prev { *dir2 } else { *dir1 };
+        prev = current;
+        current = next;
+    }
+    Some(tiles)
+}
+
+/// The number of tiles enclosed by the loop, without looking at any tile that is not part of it.
+pub fn enclosed_area_shoelace(start: Node, edges: &Edges) -> Option<usize> {
+    let tiles = find_loop(start, edges)?;
+    // twice the area of the polygon through the centres of the loop tiles
+    let twice_area: i64 = tiles
+        .iter()
+        .zip(tiles.iter().cycle().skip(1))
+        .map(|((ax, ay), (bx, by))| *ax as i64 * *by as i64 - *bx as i64 * *ay as i64)
+        .sum::<i64>()
+        .abs();
+    // Pick's theorem: area = inside + boundary / 2 - 1, every loop tile is a boundary point
+    let inside = (twice_area - tiles.len() as i64) / 2 + 1;
+    usize::try_from(inside).ok()
+}
+
+/// The number of tiles enclosed by the loop, counted with the given method.
+pub fn enclosed_area_with(start: Node, edges: &Edges, method: AreaMethod) -> Option<usize> {
+    match method {
+        AreaMethod::FloodFill => enclosed_area(start, edges),
+        AreaMethod::Shoelace => enclosed_area_shoelace(start, edges),
+    }
+}
@@ -0,0 +1,145 @@
+//! Day 11: Cosmic Expansion
+//!
+//! Distances between galaxies in an expanding universe.
+
+use common::{Answer, ErrorKind, ParseError, Solution};
+use grid::Grid;
+/// Solves both parts of the puzzle, see [`Solution`].
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 11;
+
+    type Input<'a> = Vec<Pos>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Answer {
+        distance_sum(&adjust_space(input.clone(), 2)).into()
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Answer {
+        distance_sum(&adjust_space(input.clone(), 1000000)).into()
+    }
+}
+
+/// The position of a galaxy as `(x, y)`.
+pub type Pos = (usize, usize);
+
+/// Parses an image into the positions of its galaxies.
+pub fn parse(input: &str) -> Result<Vec<Pos>, ParseError> {
+    let image = Grid::parse(input, |c| match c {
+        '#' => Ok(true),
+        '.' => Ok(false),
+        _ => Err(ErrorKind::Unknown("pixel")),
+    })?;
+    Ok(image
+        .iter()
+        .filter(|(_, galaxy)| **galaxy)
+        .map(|(pos, _)| pos)
+        .collect())
+}
+
+/// Expands the space: every empty row and column is replaced by `factor` empty ones.
+pub fn adjust_space(mut stars: Vec<Pos>, factor: usize) -> Vec<Pos> {
+    if stars.len() < 2 {
+        return stars;
+    }
+
+    stars.sort_unstable_by_key(|(x, _)| *x);
+    let mut empty_rows: Vec<(usize, usize)> = Vec::with_capacity(stars.len() * 10);
+    for i in 0..(stars.len() - 1) {
+        let x1 = stars[i].0;
+        let x2 = stars[i + 1].0;
+        if x2 - x1 > 1 {
+            empty_rows.push((i, x2 - x1));
+        }
+    }
+    for (i, star) in stars.iter_mut().enumerate() {
+        for (j, diff) in &empty_rows {
+            if i > *j {
+                star.0 += (diff - 1) * (factor - 1);
+            }
+        }
+    }
+
+    stars.sort_unstable_by_key(|(_, y)| *y);
+    empty_rows.clear();
+    let mut empty_cols: Vec<(usize, usize)> = empty_rows;
+    for i in 0..(stars.len() - 1) {
+        let y1 = stars[i].1;
+        let y2 = stars[i + 1].1;
+        if y2 - y1 > 1 {
+            empty_cols.push((i, y2 - y1));
+        }
+    }
+    for (i, star) in stars.iter_mut().enumerate() {
+        for (j, diff) in &empty_cols {
+            if i > *j {
+                star.1 += (diff - 1) * (factor - 1);
+            }
+        }
+    }
+
+    stars
+}
+
+/// Sums up the Manhattan distances between all pairs of galaxies.
+pub fn distance_sum(stars: &[Pos]) -> usize {
+    stars
+        .iter()
+        .enumerate()
+        .map(|(i, (x1, y1))| {
+            stars
+                .iter()
+                .skip(i + 1)
+                .map(|(x2, y2)| x1.max(x2) - x1.min(x2) + y1.max(y2) - y1.min(y2))
+                .sum::<usize>()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = r#"...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....
+"#;
+
+    #[test]
+    fn distance_sum_works_for_example() {
+        // given
+        let stars = parse(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let stars = adjust_space(stars, 2);
+        let distances = distance_sum(&stars);
+
+        // then
+        assert_eq!(distances, 374);
+    }
+
+    #[test]
+    fn distance_sum_works_for_example_with_large_expansion() {
+        // given
+        let stars = parse(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let stars = adjust_space(stars, 10);
+        let distances = distance_sum(&stars);
+
+        // then
+        assert_eq!(distances, 1030);
+    }
+}
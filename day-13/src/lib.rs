@@ -0,0 +1,265 @@
+//! Day 13: Point of Incidence
+//!
+//! Patterns of ash and rocks, and the lines they are mirrored at.
+
+use common::{Answer, ErrorKind, ParseError, Solution};
+use grid::Grid;
+
+/// Solves both parts of the puzzle, see [`Solution`].
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 13;
+
+    type Input<'a> = Vec<Pattern>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Answer {
+        reflection_line_sum(input).into()
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Answer {
+        line_sum_with_repaired_smudge(input.clone()).into()
+    }
+}
+
+/// A tile of a pattern.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Tile {
+    Ash,
+    Rock,
+}
+
+impl Tile {
+    fn invert(&self) -> Self {
+        match self {
+            Tile::Ash => Tile::Rock,
+            Tile::Rock => Tile::Ash,
+        }
+    }
+}
+
+/// A pattern of ash and rocks.
+pub type Pattern = Grid<Tile>;
+
+/// Sums up the columns left of the vertical and 100 times the rows above the horizontal reflection
+/// line of every pattern.
+pub fn reflection_line_sum(patterns: &[Pattern]) -> usize {
+    patterns
+        .iter()
+        .filter_map(|pattern| {
+            v_reflection_line(pattern)
+                .next()
+                .or_else(|| h_reflection_line(pattern).next().map(|y| y * 100))
+        })
+        .sum()
+}
+
+/// All vertical reflection lines, as the number of columns left of the line.
+pub fn v_reflection_line(pattern: &Pattern) -> impl Iterator<Item = usize> + '_ {
+    (1..pattern.width()).filter(|ref_x| v_reflects_at(pattern, *ref_x))
+}
+
+fn v_reflects_at(pattern: &Pattern, ref_x: usize) -> bool {
+    if ref_x == 0 || ref_x >= pattern.width() {
+        return false;
+    }
+    let mut matches = true;
+    if ref_x <= pattern.width() / 2 {
+        for x in 0..ref_x {
+            for y in 0..pattern.height() {
+                matches = matches && pattern.get(x, y) == pattern.get(ref_x * 2 - x - 1, y);
+            }
+        }
+    } else {
+        for x in ref_x..pattern.width() {
+            for y in 0..pattern.height() {
+                matches = matches && pattern.get(x, y) == pattern.get(ref_x * 2 - x - 1, y)
+            }
+        }
+    }
+    matches
+}
+
+/// All horizontal reflection lines, as the number of rows above the line.
+pub fn h_reflection_line(pattern: &Pattern) -> impl Iterator<Item = usize> + '_ {
+    (1..pattern.height()).filter(|ref_y| h_reflects_at(pattern, *ref_y))
+}
+
+fn h_reflects_at(pattern: &Pattern, ref_y: usize) -> bool {
+    if ref_y == 0 || ref_y >= pattern.height() {
+        return false;
+    }
+    let mut matches = true;
+    if ref_y <= pattern.height() / 2 {
+        for y in 0..ref_y {
+            for x in 0..pattern.width() {
+                matches = matches && pattern.get(x, y) == pattern.get(x, ref_y * 2 - y - 1);
+            }
+        }
+    } else {
+        for y in ref_y..pattern.height() {
+            for x in 0..pattern.width() {
+                matches = matches && pattern.get(x, y) == pattern.get(x, ref_y * 2 - y - 1)
+            }
+        }
+    }
+    matches
+}
+
+/// Like [`reflection_line_sum`], but with the one smudge in every pattern fixed that leads to a
+/// new reflection line.
+pub fn line_sum_with_repaired_smudge(patterns: Vec<Pattern>) -> usize {
+    patterns
+        .into_iter()
+        .filter_map(line_with_repaired_smudge)
+        .sum()
+}
+
+/// The summary of the reflection line that is new after fixing the smudge in the pattern.
+pub fn line_with_repaired_smudge(mut pattern: Pattern) -> Option<usize> {
+    // brute force again…
+    let orig_line_v = v_reflection_line(&pattern).next();
+    let orig_line_h = h_reflection_line(&pattern).next();
+    for x in 0..pattern.width() {
+        for y in 0..pattern.height() {
+            pattern[(x, y)] = pattern[(x, y)].invert();
+            if let Some(line_v) =
+                v_reflection_line(&pattern).find(|line_v| Some(*line_v) != orig_line_v)
+            {
+                return Some(line_v);
+            }
+            if let Some(line_h) =
+                h_reflection_line(&pattern).find(|line_h| Some(*line_h) != orig_line_h)
+            {
+                return Some(line_h * 100);
+            }
+            pattern[(x, y)] = pattern[(x, y)].invert();
+        }
+    }
+    None
+}
+
+/// Parses patterns separated by empty lines.
+pub fn parse(input: &str) -> Result<Vec<Pattern>, ParseError> {
+    input
+        .split("\n\n")
+        .map(|block| parse_pattern(block).map_err(|e| e.within(input, block)))
+        .collect()
+}
+
+/// Parses a single pattern.
+pub fn parse_pattern(block: &str) -> Result<Pattern, ParseError> {
+    Grid::parse(block, |c| match c {
+        '.' => Ok(Tile::Ash),
+        '#' => Ok(Tile::Rock),
+        _ => Err(ErrorKind::Unknown("tile")),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = r#"#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#
+"#;
+
+    const REV_EXAMPLE: &str = r#".##..##.#
+.#.##.#..
+#......##
+#......##
+.#.##.#..
+.##..##..
+.#.##.#.#
+
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#
+#...##..#
+"#;
+
+    const LARGE_EXAMPLE: &str = r#"##.##.####.##.#..
+..#.#..#..##.#..#
+.###...###.##.#..
+.#..#.###.#....#.
+###.##.#...##.##.
+#####.##.###..###
+#.##..#.#.#.#.#..
+#.##..#.#.#.#.#..
+#####.##.###..###
+###.##.#...##.##.
+.#.##.###.#....#.
+.###...###.##.#..
+..#.#..#..##.#..#
+##.##.####.##.#..
+##.##.####.##.#..
+"#;
+
+    #[test]
+    fn reflection_line_sum_works_for_example() {
+        // given
+        let patterns = parse(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let sum = reflection_line_sum(&patterns);
+
+        // then
+        assert_eq!(sum, 405);
+    }
+
+    #[test]
+    fn reflection_line_sum_works_for_reverse_example() {
+        // given
+        let patterns = parse(REV_EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let sum = reflection_line_sum(&patterns);
+
+        // then
+        assert_eq!(sum, 304);
+    }
+
+    #[test]
+    fn h_reflection_line_works_for_large_example() {
+        // given
+        let pattern = parse_pattern(LARGE_EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let line = h_reflection_line(&pattern).next();
+
+        // then
+        assert_eq!(line, Some(14));
+    }
+
+    #[test]
+    fn line_sum_with_repaired_smudge_works_for_example() {
+        // given
+        let patterns = parse(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let sum = line_sum_with_repaired_smudge(patterns);
+
+        // then
+        assert_eq!(sum, 400);
+    }
+}
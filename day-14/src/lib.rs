@@ -0,0 +1,319 @@
+//! Day 14: Parabolic Reflector Dish
+//!
+//! Round rocks rolling around on a tilted platform.
+
+use common::{Answer, ErrorKind, ParseError, Solution};
+use grid::Grid;
+use std::collections::HashMap;
+
+/// Solves both parts of the puzzle, see [`Solution`].
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 14;
+
+    type Input<'a> = Platform;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Answer {
+        load(&tilt_north(input.clone())).into()
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Answer {
+        spin_cycles(input.clone()).into()
+    }
+}
+
+/// A tile of the platform: empty, a cube-shaped rock or a round rock.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Tile {
+    Floor,
+    Cube,
+    Round,
+}
+
+/// A platform with rocks on it.
+pub type Platform = Grid<Tile>;
+
+/// Parses the platform, `#` is a cube-shaped and `O` a round rock.
+pub fn parse(input: &str) -> Result<Platform, ParseError> {
+    Grid::parse(input, |c| match c {
+        '.' => Ok(Tile::Floor),
+        '#' => Ok(Tile::Cube),
+        'O' => Ok(Tile::Round),
+        _ => Err(ErrorKind::Unknown("tile")),
+    })
+}
+
+/// Tilts the platform so that all round rocks roll north as far as possible.
+pub fn tilt_north(mut platform: Platform) -> Platform {
+    for x in 0..platform.width() {
+        let mut y = 0;
+        while y < platform.height() {
+            let tile = platform.get(x, y);
+            if tile == Some(&Tile::Floor) {
+                for round_y in y + 1..platform.height() {
+                    match platform.get(x, round_y) {
+                        Some(Tile::Cube) => {
+                            y = round_y;
+                            break;
+                        }
+                        Some(Tile::Round) => {
+                            platform.set(x, y, Tile::Round);
+                            platform.set(x, round_y, Tile::Floor);
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            y += 1;
+        }
+    }
+    platform
+}
+
+/// Tilts the platform so that all round rocks roll west as far as possible.
+pub fn tilt_west(mut platform: Platform) -> Platform {
+    for y in 0..platform.height() {
+        let mut x = 0;
+        while x < platform.width() {
+            let tile = platform.get(x, y);
+            if tile == Some(&Tile::Floor) {
+                for round_x in x + 1..platform.width() {
+                    match platform.get(round_x, y) {
+                        Some(Tile::Cube) => {
+                            x = round_x;
+                            break;
+                        }
+                        Some(Tile::Round) => {
+                            platform.set(x, y, Tile::Round);
+                            platform.set(round_x, y, Tile::Floor);
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            x += 1;
+        }
+    }
+    platform
+}
+
+/// Tilts the platform so that all round rocks roll east as far as possible.
+pub fn tilt_east(mut platform: Platform) -> Platform {
+    for y in 0..platform.height() {
+        let mut x = 0;
+        while x < platform.width() {
+            let tile = platform.get(platform.width() - x - 1, y);
+            if tile == Some(&Tile::Floor) {
+                for round_x in x + 1..platform.width() {
+                    match platform.get(platform.width() - round_x - 1, y) {
+                        Some(Tile::Cube) => {
+                            x = round_x;
+                            break;
+                        }
+                        Some(Tile::Round) => {
+                            platform.set(platform.width() - x - 1, y, Tile::Round);
+                            platform.set(platform.width() - round_x - 1, y, Tile::Floor);
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            x += 1;
+        }
+    }
+    platform
+}
+
+/// Tilts the platform so that all round rocks roll south as far as possible.
+pub fn tilt_south(mut platform: Platform) -> Platform {
+    for x in 0..platform.width() {
+        let mut y = 0;
+        while y < platform.height() {
+            let tile = platform.get(x, platform.height() - 1 - y);
+            if tile == Some(&Tile::Floor) {
+                for round_y in y + 1..platform.height() {
+                    match platform.get(x, platform.height() - 1 - round_y) {
+                        Some(Tile::Cube) => {
+                            y = round_y;
+                            break;
+                        }
+                        Some(Tile::Round) => {
+                            platform.set(x, platform.height() - 1 - y, Tile::Round);
+                            platform.set(x, platform.height() - 1 - round_y, Tile::Floor);
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            y += 1;
+        }
+    }
+    platform
+}
+
+/// The number of spin cycles in part two.
+pub const RUN_LENGTH: usize = 1_000_000_000;
+/// The load on the north support beams after [`RUN_LENGTH`] spin cycles (north, west, south,
+/// east).
+pub fn spin_cycles(mut platform: Platform) -> usize {
+    let mut seen: HashMap<Platform, usize> = HashMap::with_capacity(1024);
+    let mut i: usize = 0;
+    while i < RUN_LENGTH {
+        platform = tilt_north(platform);
+        platform = tilt_west(platform);
+        platform = tilt_south(platform);
+        platform = tilt_east(platform);
+        if let Some(cycle_start) = seen.get(&platform) {
+            let cycle_length = i - cycle_start;
+            i = cycle_start + ((RUN_LENGTH - cycle_start) / cycle_length) * cycle_length + 1;
+            break;
+        }
+        seen.insert(platform.clone(), i);
+        i += 1;
+    }
+    for _ in i..RUN_LENGTH {
+        platform = tilt_north(platform);
+        platform = tilt_west(platform);
+        platform = tilt_south(platform);
+        platform = tilt_east(platform);
+    }
+    load(&platform)
+}
+
+/// The load on the north support beams.
+pub fn load(platform: &Platform) -> usize {
+    let height = platform.height();
+    platform
+        .iter()
+        .filter(|(_, tile)| **tile == Tile::Round)
+        .map(|((_, y), _)| height - y)
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = r#"O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....
+"#;
+
+    const EXAMPLE_TILTED: &str = r#"OOOO.#.O..
+OO..#....#
+OO..O##..O
+O..#.OO...
+........#.
+..#....#.#
+..O..#.O.O
+..O.......
+#....###..
+#....#....
+"#;
+
+    const EXAMPLE_TILTED_WEST: &str = r#"O....#....
+OOO.#....#
+.....##...
+OO.#OO....
+OO......#.
+O.#O...#.#
+O....#OO..
+O.........
+#....###..
+#OO..#....
+"#;
+
+    #[test]
+    fn tilt_north_works_for_example() {
+        // given
+        let platform = parse(EXAMPLE).expect("expected successful parsing");
+        let platform_tilted = parse(EXAMPLE_TILTED).expect("expected successful parsing");
+
+        // when
+        let result = tilt_north(platform);
+
+        // then
+        assert_eq!(result, platform_tilted);
+    }
+
+    #[test]
+    fn load_works_for_example() {
+        // given
+        let platform = parse(EXAMPLE_TILTED).expect("expected successful parsing");
+
+        // when
+        let result = load(&platform);
+
+        // then
+        assert_eq!(result, 136);
+    }
+
+    #[test]
+    fn round_trip_tilt_north_and_south_works_for_example() {
+        // given
+        let platform = parse(EXAMPLE).expect("expected successful parsing");
+        let platform_tilted = parse(EXAMPLE_TILTED).expect("expected successful parsing");
+
+        // when
+        let platform = tilt_south(platform);
+        let result = tilt_north(platform);
+
+        // then
+        assert_eq!(result, platform_tilted);
+    }
+
+    #[test]
+    fn tilt_west_works_for_example() {
+        // given
+        let platform = parse(EXAMPLE).expect("expected successful parsing");
+        let platform_tilted = parse(EXAMPLE_TILTED_WEST).expect("expected successful parsing");
+
+        // when
+        let result = tilt_west(platform);
+
+        // then
+        assert_eq!(result, platform_tilted);
+    }
+
+    #[test]
+    fn round_trop_tilt_west_and_east_works_for_example() {
+        // given
+        let platform = parse(EXAMPLE).expect("expected successful parsing");
+        let platform_tilted = parse(EXAMPLE_TILTED_WEST).expect("expected successful parsing");
+
+        // when
+        let tilted_east = tilt_east(platform);
+        let result = tilt_west(tilted_east);
+
+        // then
+        assert_eq!(result, platform_tilted);
+    }
+
+    #[test]
+    fn spin_cycles_works_for_example() {
+        // given
+        let platform = parse(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let load = spin_cycles(platform);
+
+        // then
+        assert_eq!(load, 64);
+    }
+}
@@ -0,0 +1,141 @@
+//! Day 15: Lens Library
+//!
+//! The HASH algorithm and the lens boxes it is used for.
+
+use common::{parse_number, Answer, ErrorKind, ParseError, Solution};
+/// Solves both parts of the puzzle, see [`Solution`].
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 15;
+
+    type Input<'a> = (&'a str, Vec<(&'a str, Op)>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok((input, parse(input)?))
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Answer {
+        hash_sum(input.0).into()
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Answer {
+        focusing_power(&input.1).into()
+    }
+}
+
+/// Either remove the lens with a label or put one with a focal length in its box.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Op {
+    Dash,
+    Eq(u32),
+}
+
+/// Parses the comma-separated steps into labels and their operations.
+pub fn parse(input: &str) -> Result<Vec<(&str, Op)>, ParseError> {
+    input
+        .split(',')
+        .map(|op| parse_operation(input, op.trim()))
+        .collect()
+}
+
+// op is a slice of input, which is only needed to find the position of errors
+fn parse_operation<'a>(input: &str, op: &'a str) -> Result<(&'a str, Op), ParseError> {
+    if let Some(label) = op.strip_suffix('-') {
+        Ok((label, Op::Dash))
+    } else if let Some((label, focal_length)) = op.split_once('=') {
+        Ok((label, Op::Eq(parse_number(input, focal_length)?)))
+    } else {
+        Err(ParseError::at(
+            input,
+            op,
+            ErrorKind::Missing("operation '-' or '='"),
+        ))
+    }
+}
+
+/// Sums up the hashes of all comma-separated steps.
+pub fn hash_sum(input: &str) -> u32 {
+    input.split(',').map(hash).sum()
+}
+
+/// The HASH of a string, a number between 0 and 255.
+pub fn hash(input: &str) -> u32 {
+    input
+        .trim()
+        .bytes()
+        .fold(0u32, |current, c| ((current + c as u32) * 17) % 256)
+}
+
+/// The lenses in all 256 boxes after running the operations, as labels and focal lengths.
+pub fn run_operations<'a>(operations: &[(&'a str, Op)]) -> Vec<Vec<(&'a str, u32)>> {
+    // no efficient data structures for now, maybe this is fast enough
+    let mut boxes: Vec<Vec<(&str, u32)>> = (0..256).map(|_| vec![]).collect();
+
+    for (label, op) in operations {
+        let box_i = hash(label) as usize;
+        match op {
+            Op::Eq(focal_length) => {
+                if let Some(i) = boxes[box_i].iter().position(|(l, _)| l == label) {
+                    boxes[box_i][i].1 = *focal_length;
+                } else {
+                    boxes[box_i].push((label, *focal_length));
+                }
+            }
+            Op::Dash => {
+                if let Some(i) = boxes[box_i].iter().position(|(l, _)| l == label) {
+                    boxes[box_i].remove(i);
+                }
+            }
+        }
+    }
+    boxes
+}
+
+/// The focusing power of all lenses after running the operations.
+pub fn focusing_power(operations: &[(&str, Op)]) -> usize {
+    let boxes = run_operations(operations);
+    boxes
+        .iter()
+        .enumerate()
+        .flat_map(|(box_i, lens_box)| {
+            lens_box
+                .iter()
+                .enumerate()
+                .map(move |(lens_i, (_, focal_length))| {
+                    (box_i + 1) * (lens_i + 1) * *focal_length as usize
+                })
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n";
+
+    #[test]
+    fn hash_sum_works_for_example() {
+        // given
+        let input = EXAMPLE;
+
+        // when
+        let sum = hash_sum(input);
+
+        // then
+        assert_eq!(sum, 1320);
+    }
+
+    #[test]
+    fn focusing_power_works_for_example() {
+        // given
+        let operations = parse(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let power = focusing_power(&operations);
+
+        // then
+        assert_eq!(power, 145);
+    }
+}
@@ -0,0 +1,252 @@
+//! Day 16: The Floor Will Be Lava
+//!
+//! A beam of light bouncing around between mirrors and splitters.
+
+use common::{Answer, ErrorKind, ParseError, Solution};
+use grid::Grid;
+use std::collections::HashSet;
+
+/// Solves both parts of the puzzle, see [`Solution`].
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 16;
+
+    type Input<'a> = Contraption;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Answer {
+        energized_tiles(input, 0, 0, Dir::East).into()
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Answer {
+        maximize_energized(input).into()
+    }
+}
+
+/// A tile of the contraption: empty space, a mirror or a splitter.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Tile {
+    Empty,
+    MirrorSW,
+    MirrorNW,
+    SplitterH,
+    SplitterV,
+}
+
+/// The direction a beam travels in.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Dir {
+    North,
+    East,
+    South,
+    West,
+}
+
+/// A grid of mirrors and splitters.
+pub type Contraption = Grid<Tile>;
+
+/// Parses the contraption, with `/` and `\` as mirrors and `|` and `-` as splitters.
+pub fn parse(input: &str) -> Result<Contraption, ParseError> {
+    Grid::parse(input, |c| match c {
+        '.' => Ok(Tile::Empty),
+        '\\' => Ok(Tile::MirrorSW),
+        '/' => Ok(Tile::MirrorNW),
+        '|' => Ok(Tile::SplitterH),
+        '-' => Ok(Tile::SplitterV),
+        _ => Err(ErrorKind::Unknown("tile")),
+    })
+}
+
+/// The number of tiles a beam entering at the given position and direction passes through.
+pub fn energized_tiles(
+    contraption: &Contraption,
+    start_x: usize,
+    start_y: usize,
+    start_dir: Dir,
+) -> usize {
+    let mut queue: Vec<(usize, usize, Dir)> = Vec::with_capacity(contraption.len());
+    let mut visited: HashSet<(usize, usize, Dir)> = HashSet::with_capacity(contraption.len());
+    let mut energized: HashSet<(usize, usize)> = HashSet::with_capacity(contraption.len());
+    if contraption.is_empty() {
+        return 0;
+    }
+
+    queue.push((start_x, start_y, start_dir));
+    while let Some((x, y, dir)) = queue.pop() {
+        if visited.contains(&(x, y, dir)) {
+            continue;
+        }
+        if let Some(tile) = contraption.get(x, y) {
+            visited.insert((x, y, dir));
+            energized.insert((x, y));
+            match dir {
+                Dir::North => match tile {
+                    Tile::Empty | Tile::SplitterH => {
+                        if y > 0 {
+                            queue.push((x, y - 1, Dir::North));
+                        }
+                    }
+                    Tile::MirrorSW => {
+                        if x > 0 {
+                            queue.push((x - 1, y, Dir::West));
+                        }
+                    }
+                    Tile::MirrorNW => {
+                        if x + 1 < contraption.width() {
+                            queue.push((x + 1, y, Dir::East));
+                        }
+                    }
+                    Tile::SplitterV => {
+                        if x > 0 {
+                            queue.push((x - 1, y, Dir::West));
+                        }
+                        if x + 1 < contraption.width() {
+                            queue.push((x + 1, y, Dir::East));
+                        }
+                    }
+                },
+                Dir::South => match tile {
+                    Tile::Empty | Tile::SplitterH => {
+                        if y + 1 < contraption.height() {
+                            queue.push((x, y + 1, Dir::South));
+                        }
+                    }
+                    Tile::MirrorSW => {
+                        if x + 1 < contraption.width() {
+                            queue.push((x + 1, y, Dir::East));
+                        }
+                    }
+                    Tile::MirrorNW => {
+                        if x > 0 {
+                            queue.push((x - 1, y, Dir::West));
+                        }
+                    }
+                    Tile::SplitterV => {
+                        if x > 0 {
+                            queue.push((x - 1, y, Dir::West));
+                        }
+                        if x + 1 < contraption.width() {
+                            queue.push((x + 1, y, Dir::East));
+                        }
+                    }
+                },
+                Dir::West => match tile {
+                    Tile::Empty | Tile::SplitterV => {
+                        if x > 0 {
+                            queue.push((x - 1, y, Dir::West));
+                        }
+                    }
+                    Tile::MirrorSW => {
+                        if y > 0 {
+                            queue.push((x, y - 1, Dir::North));
+                        }
+                    }
+                    Tile::MirrorNW => {
+                        if y + 1 < contraption.height() {
+                            queue.push((x, y + 1, Dir::South));
+                        }
+                    }
+                    Tile::SplitterH => {
+                        if y > 0 {
+                            queue.push((x, y - 1, Dir::North));
+                        }
+                        if y + 1 < contraption.height() {
+                            queue.push((x, y + 1, Dir::South));
+                        }
+                    }
+                },
+                Dir::East => match tile {
+                    Tile::Empty | Tile::SplitterV => {
+                        if x + 1 < contraption.width() {
+                            queue.push((x + 1, y, Dir::East));
+                        }
+                    }
+                    Tile::MirrorSW => {
+                        if y + 1 < contraption.height() {
+                            queue.push((x, y + 1, Dir::South));
+                        }
+                    }
+                    Tile::MirrorNW => {
+                        if y > 0 {
+                            queue.push((x, y - 1, Dir::North));
+                        }
+                    }
+                    Tile::SplitterH => {
+                        if y > 0 {
+                            queue.push((x, y - 1, Dir::North));
+                        }
+                        if y + 1 < contraption.height() {
+                            queue.push((x, y + 1, Dir::South));
+                        }
+                    }
+                },
+            }
+        }
+    }
+
+    energized.len()
+}
+
+/// The highest number of energized tiles over all beams that enter from any edge.
+pub fn maximize_energized(contraption: &Contraption) -> usize {
+    (0..contraption.width())
+        .flat_map(|x| {
+            [
+                energized_tiles(contraption, x, 0, Dir::South),
+                energized_tiles(contraption, x, contraption.height() - 1, Dir::North),
+            ]
+        })
+        .chain((0..contraption.height()).flat_map(|y| {
+            [
+                energized_tiles(contraption, 0, y, Dir::East),
+                energized_tiles(contraption, contraption.width() - 1, y, Dir::West),
+            ]
+        }))
+        .max()
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = r#".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....
+"#;
+
+    #[test]
+    fn energized_tiles_works_for_example() {
+        // given
+        let contraption = parse(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let e = energized_tiles(&contraption, 0, 0, Dir::East);
+
+        // then
+        assert_eq!(e, 46);
+    }
+
+    #[test]
+    fn maximize_energized_works_for_example() {
+        // given
+        let contraption = parse(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let e = maximize_energized(&contraption);
+
+        // then
+        assert_eq!(e, 51);
+    }
+}
@@ -0,0 +1,216 @@
+//! Day 18: Lavaduct Lagoon
+//!
+//! The size of a lagoon dug out along a dig plan.
+
+use common::{parse_lines, parse_number, split_once, Answer, ErrorKind, ParseError, Solution};
+/// Solves both parts of the puzzle, see [`Solution`].
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 18;
+
+    type Input<'a> = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Answer {
+        dig(input).into()
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Answer {
+        fix_instructions(input)
+            .map(|instructions| dig(&instructions))
+            .into()
+    }
+}
+
+/// The direction of a dig step.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Dir {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir {
+    fn outer_angle(self, other: Self) -> bool {
+        matches!(
+            (self, other),
+            (Dir::Up, Dir::Right)
+                | (Dir::Down, Dir::Left)
+                | (Dir::Right, Dir::Down)
+                | (Dir::Left, Dir::Up)
+        )
+    }
+}
+
+/// One step of the dig plan: dig `length` meters in `dir`. `color` is the hexadecimal color code
+/// that actually contains the correct instruction.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Instruction {
+    pub dir: Dir,
+    pub length: u32,
+    pub color: u32,
+}
+
+/// Parses one instruction per line.
+pub fn parse(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse_lines(input, parse_instruction)
+}
+
+/// Parses an instruction like `R 6 (#70c710)`.
+pub fn parse_instruction(line: &str) -> Result<Instruction, ParseError> {
+    let (dir, rest) = split_once(line, line, " ", "separator after direction")?;
+    let dir = match dir {
+        "U" => Ok(Dir::Up),
+        "R" => Ok(Dir::Right),
+        "D" => Ok(Dir::Down),
+        "L" => Ok(Dir::Left),
+        _ => Err(ParseError::at(line, dir, ErrorKind::Unknown("direction"))),
+    }?;
+
+    let (length, color) = split_once(line, rest, " (#", "color")?;
+    let length: u32 = parse_number(line, length)?;
+    let color = color
+        .strip_suffix(')')
+        .ok_or_else(|| ParseError::at(line, color, ErrorKind::Missing("')' after color")))?;
+    let color = u32::from_str_radix(color, 16)
+        .map_err(|e| ParseError::at(line, color, ErrorKind::InvalidNumber(e.to_string())))?;
+
+    Ok(Instruction { dir, length, color })
+}
+
+/// The correct instruction hidden in the color of the given one.
+pub fn fix_instruction(instruction: &Instruction) -> Result<Instruction, String> {
+    let dir = match instruction.color % 16 {
+        0 => Ok(Dir::Right),
+        1 => Ok(Dir::Down),
+        2 => Ok(Dir::Left),
+        3 => Ok(Dir::Up),
+        n => Err(format!("unknown direction: '{n}'")),
+    }?;
+
+    let length = instruction.color / 16;
+
+    Ok(Instruction {
+        dir,
+        length,
+        color: instruction.color,
+    })
+}
+
+/// Fixes all instructions, see [`fix_instruction`].
+pub fn fix_instructions(instructions: &[Instruction]) -> Result<Vec<Instruction>, String> {
+    instructions.iter().map(fix_instruction).collect()
+}
+
+/// The number of cubic meters of the lagoon, including the trench and its interior.
+pub fn dig(instructions: &[Instruction]) -> i64 {
+    if instructions.is_empty() {
+        return 0;
+    }
+
+    let mut pos: (i64, i64) = (0, 0);
+    let mut previous_dir = instructions[instructions.len() - 1].dir;
+    let mut corners: Vec<(i64, i64)> = Vec::with_capacity(instructions.len() + 1);
+    corners.push(pos);
+
+    // assumption: trenches are dug clockwise
+    for pair in instructions.windows(2) {
+        let Instruction {
+            dir,
+            length,
+            color: _,
+        } = pair[0];
+        let next_dir = pair[1].dir;
+        match dir {
+            Dir::Up => {
+                pos.1 -= length as i64 - 1
+                    + if previous_dir.outer_angle(dir) { 1 } else { 0 }
+                    + if dir.outer_angle(next_dir) { 1 } else { 0 };
+                corners.push(pos);
+            }
+            Dir::Right => {
+                pos.0 += length as i64 - 1
+                    + if previous_dir.outer_angle(dir) { 1 } else { 0 }
+                    + if dir.outer_angle(next_dir) { 1 } else { 0 };
+                corners.push(pos);
+            }
+            Dir::Down => {
+                pos.1 += length as i64 - 1
+                    + if previous_dir.outer_angle(dir) { 1 } else { 0 }
+                    + if dir.outer_angle(next_dir) { 1 } else { 0 };
+                corners.push(pos);
+            }
+            Dir::Left => {
+                pos.0 -= length as i64 - 1
+                    + if previous_dir.outer_angle(dir) { 1 } else { 0 }
+                    + if dir.outer_angle(next_dir) { 1 } else { 0 };
+                corners.push(pos);
+            }
+        }
+        previous_dir = dir;
+    }
+
+    corners.push((0, 0));
+
+    (corners
+        .windows(2)
+        .map(|win| {
+            let (x1, y1) = win[0];
+            let (x2, y2) = win[1];
+            (y1 + y2) * (x1 - x2)
+        })
+        .sum::<i64>()
+        / 2)
+    .abs()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = r#"R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)
+"#;
+
+    #[test]
+    fn dig_works_for_example() {
+        // given
+        let instructions = parse(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let dug_out = dig(&instructions);
+
+        // then
+        assert_eq!(dug_out, 62);
+    }
+
+    #[test]
+    fn dig_works_for_fixed_example() {
+        // given
+        let instructions = fix_instructions(&parse(EXAMPLE).expect("expected successful parsing"))
+            .expect("expected successful correction");
+
+        // when
+        let dug_out = dig(&instructions);
+
+        // then
+        assert_eq!(dug_out, 952408144115);
+    }
+}
@@ -0,0 +1,507 @@
+//! Day 19: Aplenty
+//!
+//! Machine parts sorted through workflows until they are accepted or rejected.
+
+use common::{parse_number, split_once, Answer, ErrorKind, ParseError, Solution};
+use std::collections::HashMap;
+
+/// Solves both parts of the puzzle, see [`Solution`].
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 19;
+
+    type Input<'a> = (Vec<Part>, HashMap<&'a str, Workflow<'a>>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Answer {
+        let (parts, workflows) = input;
+        accepted_rating(parts, workflows).into()
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Answer {
+        let (_, workflows) = input;
+        find_combinations(workflows, LOWEST_PART, HIGHEST_PART, "in").into()
+    }
+}
+
+const LOWEST_PART: Part = Part {
+    extremely_cool: 1,
+    musical: 1,
+    aerodynamic: 1,
+    shiny: 1,
+};
+const HIGHEST_PART: Part = Part {
+    extremely_cool: 4001,
+    musical: 4001,
+    aerodynamic: 4001,
+    shiny: 4001,
+};
+
+/// The number of parts with ratings between `lower` and `upper` (exclusive) that are accepted when
+/// starting at the workflow `name`.
+///
+/// This uses `u128` to take no risks with the integer size of the combinations.
+pub fn find_combinations(
+    workflows: &HashMap<&str, Workflow>,
+    mut lower: Part,
+    mut upper: Part,
+    name: &str,
+) -> Result<u128, String> {
+    // if the lower bound reached the upper bound anywhere, there are no possible combinations
+    if lower.extremely_cool >= upper.extremely_cool
+        || lower.musical >= upper.musical
+        || lower.aerodynamic >= upper.aerodynamic
+        || lower.shiny >= upper.shiny
+    {
+        return Ok(0);
+    }
+    let workflow = workflows
+        .get(name)
+        .ok_or_else(|| format!("unable to find workflow with name '{name}'"))?;
+    let mut combinations: u128 = 0;
+    for (cond_op, cond_value, outcome) in &workflow.rules {
+        let cond_op = *cond_op;
+        let cond_value = *cond_value;
+        let outcome = *outcome;
+        let (matched_lower, matched_upper) = match cond_op {
+            Condition::XGt => {
+                let orig_upper = upper;
+                upper.extremely_cool = cond_value + 1;
+                (
+                    Part {
+                        extremely_cool: cond_value + 1,
+                        ..lower
+                    },
+                    orig_upper,
+                )
+            }
+            Condition::XLt => {
+                let orig_lower = lower;
+                lower.extremely_cool = cond_value;
+                (
+                    orig_lower,
+                    Part {
+                        extremely_cool: cond_value,
+                        ..upper
+                    },
+                )
+            }
+            Condition::MGt => {
+                let orig_upper = upper;
+                upper.musical = cond_value + 1;
+                (
+                    Part {
+                        musical: cond_value + 1,
+                        ..lower
+                    },
+                    orig_upper,
+                )
+            }
+            Condition::MLt => {
+                let orig_lower = lower;
+                lower.musical = cond_value;
+                (
+                    orig_lower,
+                    Part {
+                        musical: cond_value,
+                        ..upper
+                    },
+                )
+            }
+            Condition::AGt => {
+                let orig_upper = upper;
+                upper.aerodynamic = cond_value + 1;
+                (
+                    Part {
+                        aerodynamic: cond_value + 1,
+                        ..lower
+                    },
+                    orig_upper,
+                )
+            }
+            Condition::ALt => {
+                let orig_lower = lower;
+                lower.aerodynamic = cond_value;
+                (
+                    orig_lower,
+                    Part {
+                        aerodynamic: cond_value,
+                        ..upper
+                    },
+                )
+            }
+            Condition::SGt => {
+                let orig_upper = upper;
+                upper.shiny = cond_value + 1;
+                (
+                    Part {
+                        shiny: cond_value + 1,
+                        ..lower
+                    },
+                    orig_upper,
+                )
+            }
+            Condition::SLt => {
+                let orig_lower = lower;
+                lower.shiny = cond_value;
+                (
+                    orig_lower,
+                    Part {
+                        shiny: cond_value,
+                        ..upper
+                    },
+                )
+            }
+        };
+        combinations += match outcome {
+            Outcome::Reject => 0,
+            Outcome::Accept => part_combinations(matched_lower, matched_upper),
+            Outcome::SendTo(name) => {
+                find_combinations(workflows, matched_lower, matched_upper, name)?
+            }
+        };
+    }
+    Ok(combinations
+        + match workflow.default {
+            Outcome::Reject => 0,
+            Outcome::Accept => part_combinations(lower, upper),
+            Outcome::SendTo(name) => find_combinations(workflows, lower, upper, name)?,
+        })
+}
+
+/// The number of parts with ratings between `lower` and `upper` (exclusive).
+pub fn part_combinations(lower: Part, upper: Part) -> u128 {
+    if lower.extremely_cool >= upper.extremely_cool
+        || lower.musical >= upper.musical
+        || lower.aerodynamic >= upper.aerodynamic
+        || lower.shiny >= upper.shiny
+    {
+        return 0;
+    }
+    (upper.extremely_cool - lower.extremely_cool) as u128
+        * (upper.musical - lower.musical) as u128
+        * (upper.aerodynamic - lower.aerodynamic) as u128
+        * (upper.shiny - lower.shiny) as u128
+}
+
+/// Sums up the ratings of all accepted parts, starting at the workflow `in`.
+pub fn accepted_rating(parts: &[Part], workflows: &HashMap<&str, Workflow>) -> Result<i64, String> {
+    let mut rating: i64 = 0;
+    for part in parts {
+        if check_part(part, workflows)? {
+            rating += part.rating();
+        }
+    }
+    Ok(rating)
+}
+
+/// Whether the part is accepted, starting at the workflow `in`.
+pub fn check_part(part: &Part, workflows: &HashMap<&str, Workflow>) -> Result<bool, String> {
+    // Important: We have no loop detection here, so if the workflow results in a loop, we are
+    // stuck. If that happens, we need to implement a loop detection after all
+    let mut name: &str = "in";
+    loop {
+        let workflow = workflows
+            .get(name)
+            .ok_or_else(|| format!("unable to find workflow with name '{name}'"))?;
+        name = match check_workflow(workflow, part) {
+            Outcome::Accept => {
+                return Ok(true);
+            }
+            Outcome::Reject => {
+                return Ok(false);
+            }
+            Outcome::SendTo(n) => n,
+        };
+    }
+}
+
+fn check_workflow<'a>(workflow: &'a Workflow, part: &'a Part) -> Outcome<'a> {
+    workflow
+        .rules
+        .iter()
+        .filter(|(condition, value, _)| condition.apply(part, *value))
+        .map(|(_, _, outcome)| *outcome)
+        .next()
+        .unwrap_or(workflow.default)
+}
+
+/// Parses the workflows by name and the parts. Every workflow that can be reached is known.
+pub fn parse(input: &str) -> Result<(Vec<Part>, HashMap<&str, Workflow<'_>>), ParseError> {
+    let (workflows, parts) = split_once(
+        input,
+        input,
+        "\n\n",
+        "empty line between workflows and parts",
+    )?;
+    let parts = parts
+        .lines()
+        .map(|line| parse_part(input, line))
+        .collect::<Result<_, ParseError>>()?;
+    let workflows = workflows
+        .lines()
+        .map(|line| {
+            let workflow = parse_workflow(input, line)?;
+            Ok((workflow.name, workflow))
+        })
+        .collect::<Result<HashMap<&str, Workflow>, ParseError>>()?;
+
+    // every part starts in "in", and all workflows parts are sent to have to exist
+    if !workflows.contains_key("in") {
+        return Err(ParseError::at(
+            input,
+            &input[..0],
+            ErrorKind::Missing("workflow 'in'"),
+        ));
+    }
+    for workflow in workflows.values() {
+        let outcomes = workflow.rules.iter().map(|(_, _, outcome)| outcome);
+        for outcome in outcomes.chain([&workflow.default]) {
+            if let Outcome::SendTo(name) = outcome {
+                if !workflows.contains_key(name) {
+                    return Err(ParseError::at(input, name, ErrorKind::Unknown("workflow")));
+                }
+            }
+        }
+    }
+
+    Ok((parts, workflows))
+}
+
+/// A machine part with its ratings for each category.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Part {
+    pub extremely_cool: i64,
+    pub musical: i64,
+    pub aerodynamic: i64,
+    pub shiny: i64,
+}
+
+impl Part {
+    /// Sums up the ratings of all categories.
+    pub fn rating(&self) -> i64 {
+        self.extremely_cool + self.musical + self.aerodynamic + self.shiny
+    }
+}
+
+// line is a slice of input, which is only needed to find the position of errors
+fn parse_part(input: &str, line: &str) -> Result<Part, ParseError> {
+    let rest = line.strip_prefix("{x=").ok_or_else(|| {
+        ParseError::at(
+            input,
+            line,
+            ErrorKind::Missing("'{x=' at the start of the part"),
+        )
+    })?;
+    let (extremely_cool, rest) = split_once(input, rest, ",m=", "musical value")?;
+    let extremely_cool: i64 = parse_number(input, extremely_cool)?;
+
+    let (musical, rest) = split_once(input, rest, ",a=", "aerodynamic value")?;
+    let musical: i64 = parse_number(input, musical)?;
+
+    let (aerodynamic, shiny) = split_once(input, rest, ",s=", "shiny value")?;
+    let aerodynamic: i64 = parse_number(input, aerodynamic)?;
+
+    let shiny = shiny
+        .strip_suffix('}')
+        .ok_or_else(|| ParseError::at(input, shiny, ErrorKind::Missing("'}' after shiny value")))?;
+    let shiny: i64 = parse_number(input, shiny)?;
+
+    Ok(Part {
+        extremely_cool,
+        musical,
+        aerodynamic,
+        shiny,
+    })
+}
+
+/// A comparison of one category with a value, e.g. `XGt` for `x>…`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Condition {
+    XGt,
+    XLt,
+    MGt,
+    MLt,
+    AGt,
+    ALt,
+    SGt,
+    SLt,
+}
+
+impl Condition {
+    /// Whether the category of `left` fulfills the condition when compared with `right`.
+    pub fn apply(&self, left: &Part, right: i64) -> bool {
+        match self {
+            Condition::XGt => left.extremely_cool > right,
+            Condition::XLt => left.extremely_cool < right,
+            Condition::MGt => left.musical > right,
+            Condition::MLt => left.musical < right,
+            Condition::AGt => left.aerodynamic > right,
+            Condition::ALt => left.aerodynamic < right,
+            Condition::SGt => left.shiny > right,
+            Condition::SLt => left.shiny < right,
+        }
+    }
+}
+
+/// What happens to a part when a rule applies.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Outcome<'a> {
+    SendTo(&'a str),
+    Reject,
+    Accept,
+}
+
+/// A named list of rules, checked in order, and what happens if no rule applies.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Workflow<'a> {
+    pub name: &'a str,
+    pub rules: Vec<(Condition, i64, Outcome<'a>)>,
+    pub default: Outcome<'a>,
+}
+
+// line is a slice of input, which is only needed to find the position of errors
+fn parse_workflow<'a>(input: &str, line: &'a str) -> Result<Workflow<'a>, ParseError> {
+    let (name, rules) = split_once(input, line, "{", "rules of the workflow")?;
+
+    let (rules, default) = rules.rsplit_once(',').unwrap_or(("", rules));
+    let default = parse_outcome(default.strip_suffix('}').ok_or_else(|| {
+        ParseError::at(
+            input,
+            default,
+            ErrorKind::Missing("'}' at the end of the workflow"),
+        )
+    })?);
+
+    let rules: Vec<(Condition, i64, Outcome)> = rules
+        .split(',')
+        .map(|rule| {
+            let (condition, outcome) = split_once(input, rule, ":", "outcome of the rule")?;
+            let outcome = parse_outcome(outcome);
+
+            let (cond_op, cond_v) = parse_condition(input, condition)?;
+
+            Ok((cond_op, cond_v, outcome))
+        })
+        .collect::<Result<_, ParseError>>()?;
+
+    Ok(Workflow {
+        name,
+        rules,
+        default,
+    })
+}
+
+fn parse_outcome(s: &str) -> Outcome<'_> {
+    match s {
+        "A" => Outcome::Accept,
+        "R" => Outcome::Reject,
+        _ => Outcome::SendTo(s),
+    }
+}
+
+fn parse_condition(input: &str, s: &str) -> Result<(Condition, i64), ParseError> {
+    if let Some((variable, value)) = s.split_once('<') {
+        let condition = match variable {
+            "x" => Ok(Condition::XLt),
+            "m" => Ok(Condition::MLt),
+            "a" => Ok(Condition::ALt),
+            "s" => Ok(Condition::SLt),
+            _ => Err(ParseError::at(
+                input,
+                variable,
+                ErrorKind::Unknown("category"),
+            )),
+        }?;
+        Ok((condition, parse_number(input, value)?))
+    } else if let Some((variable, value)) = s.split_once('>') {
+        let condition = match variable {
+            "x" => Ok(Condition::XGt),
+            "m" => Ok(Condition::MGt),
+            "a" => Ok(Condition::AGt),
+            "s" => Ok(Condition::SGt),
+            _ => Err(ParseError::at(
+                input,
+                variable,
+                ErrorKind::Unknown("category"),
+            )),
+        }?;
+        Ok((condition, parse_number(input, value)?))
+    } else {
+        Err(ParseError::at(
+            input,
+            s,
+            ErrorKind::Missing("operator '<' or '>'"),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = r#"px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=291}
+{x=2127,m=1623,a=2188,s=1013}
+"#;
+
+    #[test]
+    fn accepted_rating_works_for_example() {
+        // given
+        let (parts, workflows) = parse(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let rating = accepted_rating(&parts, &workflows);
+
+        // then
+        assert_eq!(rating, Ok(19114));
+    }
+
+    #[test]
+    fn find_combinations_works_for_example() {
+        // given
+        let (_, workflows) = parse(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let n = find_combinations(&workflows, LOWEST_PART, HIGHEST_PART, "in");
+
+        // then
+        assert_eq!(n, Ok(167409079868000));
+    }
+
+    #[test]
+    fn parse_rejects_missing_workflow() {
+        // given
+        let input = EXAMPLE.replacen(
+            "qqz{s>2770:qs,m<1801:hdj,R}",
+            "qqz{s>2770:qs,m<1801:xyz,R}",
+            1,
+        );
+
+        // when
+        let error = parse(&input).expect_err("expected parsing to fail");
+
+        // then
+        assert_eq!(error.kind, ErrorKind::Unknown("workflow"));
+        assert_eq!(error.text, "xyz");
+        assert_eq!((error.line, error.column), (9, 22));
+    }
+}
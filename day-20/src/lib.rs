@@ -0,0 +1,310 @@
+//! Day 20: Pulse Propagation
+//!
+//! Pulses sent through a network of flip-flops and conjunctions.
+
+use common::{parse_lines, split_once, Answer, ErrorKind, ParseError, Solution};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Solves both parts of the puzzle, see [`Solution`].
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 20;
+
+    type Input<'a> = (Modules<'a>, Connections<'a>);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Answer {
+        let (modules, connections) = input;
+        let (low, high) = push_button_n(modules.clone(), connections, 1000);
+        (low * high).into()
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Answer {
+        let (modules, connections) = input;
+        first_rx_signal(modules.clone(), connections)
+            .map_err(|e| {
+                format!("My shortcut to get the required number of button pushes failed: {e}")
+            })
+            .into()
+    }
+}
+
+/// The number of low and high pulses sent when pushing the button `n` times.
+pub fn push_button_n(
+    mut modules: HashMap<&str, Module>,
+    connections: &HashMap<&str, Vec<&str>>,
+    n: u64,
+) -> (u64, u64) {
+    let mut low = 0;
+    let mut high = 0;
+    for _ in 0..n {
+        let (l, h, _) = push_button(&mut modules, connections);
+        low += l;
+        high += h;
+    }
+    (low, high)
+}
+
+/// The number of button pushes until `rx` receives a low pulse. This relies on the structure of
+/// the puzzle input: `rx` is fed by one conjunction, which is fed by independent cycles.
+pub fn first_rx_signal(
+    mut modules: HashMap<&str, Module>,
+    connections: &HashMap<&str, Vec<&str>>,
+) -> Result<u64, String> {
+    // we can't just push the button until rx receives a low pulse, that would take
+    // very long (for my input).
+    // However, in my input, the only module that feeds into rx ist a conjunction module that only
+    // has conjunction modules as input,and each of those has exactly _one_ conjunction module as
+    // input.
+    // So the idea is: check for loops in the inputs of the conjunction that feeds into rx, and
+    // calculate how long it would take for rx to receive a low pulse
+    let mut rx_inputs = connections
+        .iter()
+        .filter(|(_, outputs)| outputs.contains(&"rx"))
+        .map(|(name, _)| name);
+    let rx_input = rx_inputs
+        .next()
+        .ok_or_else(|| "did not find any input to rx in the data".to_string())?;
+    if rx_inputs.next().is_some() {
+        return Err("more than one input to rx, violating my assumption".to_string());
+    }
+
+    let second_order_dep: Vec<(&str, Vec<&str>)> =
+        if let Some(Module::Conjunction(inputs)) = modules.get(rx_input) {
+            inputs
+                .keys()
+                .map(|key| {
+                    if let Some(Module::Conjunction(t_inputs)) = modules.get(key) {
+                        Ok((*key, t_inputs.keys().copied().collect::<Vec<&str>>()))
+                    } else {
+                        Err(
+                    "input to rx is not a conjunction (or not present), violating my assumption"
+                        .to_string(),
+                )
+                    }
+                })
+                .collect::<Result<_, _>>()?
+        } else {
+            return Err(
+                "input to rx is not a conjunction (or not present), violating my assumption"
+                    .to_string(),
+            );
+        };
+    let inputs: Vec<(&str, Vec<&str>)> = second_order_dep
+        .iter()
+        .flat_map(|(_, names)| {
+            names.iter().map(|key| {
+                if let Some(Module::Conjunction(t_inputs)) = modules.get(key) {
+                    Ok((*key, t_inputs.keys().copied().collect::<Vec<&str>>()))
+                } else {
+                    Err(
+                    "input to rx is not a conjunction (or not present), violating my assumption"
+                        .to_string(),
+                )
+                }
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    let unique_transient_inputs = inputs
+        .iter()
+        .flat_map(|(_, ti)| ti)
+        .copied()
+        .collect::<HashSet<&str>>();
+    let mut input_cycle_count: HashMap<&str, u64> =
+        HashMap::with_capacity(unique_transient_inputs.len());
+    let mut counter: u64 = 0;
+
+    while input_cycle_count.len() < unique_transient_inputs.len() {
+        counter += 1;
+        push_button(&mut modules, connections);
+        for (t_input, _) in &inputs {
+            if let Some(Module::Conjunction(inputs)) = modules.get(t_input) {
+                for (name, pulse) in inputs {
+                    if *pulse {
+                        input_cycle_count.entry(name).or_insert(counter);
+                    }
+                }
+            } else {
+                panic!("modules changed after button push, that should not happen!");
+            }
+        }
+    }
+    Ok(inputs
+        .iter()
+        .map(|(_, sub_inputs)| {
+            // IMPORTANT: this assumes the inputs are bits of some kind of binary counter
+            sub_inputs
+                .iter()
+                .map(|sub_name| input_cycle_count.get(sub_name).unwrap())
+                .sum::<u64>()
+        })
+        // IMPORTANT: this assumes all the cycle lengths are primes
+        // if this is not the case, the LCM needs to be used here
+        .product::<u64>())
+}
+
+/// Pushes the button once, and returns the number of low and high pulses and whether `rx`
+/// received a low pulse.
+pub fn push_button(
+    modules: &mut HashMap<&str, Module>,
+    connections: &HashMap<&str, Vec<&str>>,
+) -> (u64, u64, bool) {
+    let mut low: u64 = 0;
+    let mut high: u64 = 0;
+    let mut rx_low: bool = false;
+    // false means low pulse, true means high pulse
+    let mut queue: VecDeque<(&str, bool, &str)> = VecDeque::with_capacity(modules.len());
+    queue.push_back(("broadcaster", false, "button"));
+
+    while let Some((name, pulse, sender)) = queue.pop_front() {
+        if pulse {
+            high += 1;
+        } else {
+            low += 1;
+        }
+
+        if name == "rx" {
+            rx_low = rx_low || !pulse;
+            continue;
+        }
+        match modules.get_mut(name) {
+            Some(Module::FlipFlop(state)) => {
+                if !pulse {
+                    *state = !*state;
+                    if let Some(outputs) = connections.get(name) {
+                        for out in outputs {
+                            queue.push_back((out, *state, name))
+                        }
+                    }
+                }
+            }
+            Some(Module::Conjunction(inputs)) => {
+                if let Some(state) = inputs.get_mut(sender) {
+                    *state = pulse;
+                }
+                let out_pulse = !inputs.values().all(|state| *state);
+                if let Some(outputs) = connections.get(name) {
+                    for out in outputs {
+                        queue.push_back((out, out_pulse, name))
+                    }
+                }
+            }
+            Some(Module::Broadcast) => {
+                if let Some(outputs) = connections.get(name) {
+                    for out in outputs {
+                        queue.push_back((out, pulse, name))
+                    }
+                }
+            }
+            None => {
+                eprintln!("tried to send pulse to unknown module '{name}', ignoring pulse");
+            }
+        }
+    }
+
+    (low, high, rx_low)
+}
+
+/// A module and its state.
+#[derive(Clone, Debug)]
+pub enum Module<'a> {
+    FlipFlop(bool),
+    Conjunction(HashMap<&'a str, bool>),
+    Broadcast,
+}
+
+/// The modules by name.
+pub type Modules<'a> = HashMap<&'a str, Module<'a>>;
+/// The destinations of every module by name.
+pub type Connections<'a> = HashMap<&'a str, Vec<&'a str>>;
+
+/// Parses the modules and where they send their pulses to.
+pub fn parse(input: &str) -> Result<(Modules<'_>, Connections<'_>), ParseError> {
+    let mut modules: HashMap<&str, Module> = HashMap::with_capacity(input.len());
+    let mut connections: HashMap<&str, Vec<&str>> = HashMap::with_capacity(input.len());
+
+    for (name, module, out) in parse_lines(input, parse_module)? {
+        modules.insert(name, module);
+        connections.insert(name, out);
+    }
+
+    for (from, tos) in connections.iter() {
+        for to in tos {
+            if let Some(Module::Conjunction(inputs)) = modules.get_mut(to) {
+                inputs.insert(from, false);
+            }
+        }
+    }
+    Ok((modules, connections))
+}
+
+fn parse_module(line: &str) -> Result<(&str, Module<'_>, Vec<&str>), ParseError> {
+    let (module, out) = split_once(line, line, " -> ", "' -> ' between module and its outputs")?;
+
+    let (module, name) = if module == "broadcaster" {
+        (Module::Broadcast, module)
+    } else if let Some(name) = module.strip_prefix('%') {
+        (Module::FlipFlop(false), name)
+    } else if let Some(name) = module.strip_prefix('&') {
+        (Module::Conjunction(HashMap::with_capacity(8)), name)
+    } else {
+        return Err(ParseError::at(
+            line,
+            module,
+            ErrorKind::Unknown("module type"),
+        ));
+    };
+
+    let out: Vec<&str> = out.split(", ").collect();
+
+    Ok((name, module, out))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE1: &str = r#"broadcaster -> a, b, c
+%a -> b
+%b -> c
+%c -> inv
+&inv -> a
+"#;
+    const EXAMPLE2: &str = r#"broadcaster -> a
+%a -> inv, con
+&inv -> b
+%b -> con
+&con -> output
+"#;
+
+    #[test]
+    fn push_button_n_works_for_example_1() {
+        // given
+        let (modules, connections) = parse(EXAMPLE1).expect("expected successful parsing");
+
+        // when
+        let (low, high) = push_button_n(modules, &connections, 1000);
+
+        // then
+        assert_eq!(low, 8000);
+        assert_eq!(high, 4000);
+    }
+
+    #[test]
+    fn push_button_n_works_for_example_2() {
+        // given
+        let (modules, connections) = parse(EXAMPLE2).expect("expected successful parsing");
+
+        // when
+        let (low, high) = push_button_n(modules, &connections, 1000);
+
+        // then
+        assert_eq!(low, 4250);
+        assert_eq!(high, 2750);
+    }
+}
@@ -0,0 +1,333 @@
+//! Day 21: Step Counter
+//!
+//! The garden plots an elf can reach in an exact number of steps, in a repeating garden.
+
+use common::{Answer, ErrorKind, ParseError, Solution};
+use grid::Grid;
+use std::collections::{HashMap, VecDeque};
+
+/// Solves both parts of the puzzle, see [`Solution`].
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 21;
+
+    type Input<'a> = (usize, usize, Garden);
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Answer {
+        let (start_x, start_y, garden) = input;
+        possible_plots(*start_x, *start_y, garden, 64).into()
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Answer {
+        let (start_x, start_y, garden) = input;
+        possible_plots_on_infinite_garden(*start_x, *start_y, garden, 26501365).into()
+    }
+}
+
+/// Like [`possible_plots`], but for a garden that repeats infinitely in all directions. This
+/// relies on the structure of the puzzle input, e.g. that the start is in the center and the
+/// rows and columns of the start are free.
+pub fn possible_plots_on_infinite_garden(
+    start_x: usize,
+    start_y: usize,
+    garden: &Garden,
+    steps: u128,
+) -> Result<u128, String> {
+    // idea: get the distances to the corner plots and extrapolate from there
+    // assumption: the tiles on the edge are always garden plots (they are for my input and for the
+    // example)
+    // another assumption: the input is quadratic
+    if garden.width() != garden.height() {
+        return Err("assumed that the garden is a square, but it is not".to_string());
+    }
+    for x in 0..garden.width() {
+        if garden.get(x, 0) != Some(&Tile::Plot)
+            || garden.get(0, x) != Some(&Tile::Plot)
+            || garden.get(x, garden.width() - 1) != Some(&Tile::Plot)
+            || garden.get(garden.width() - 1, x) != Some(&Tile::Plot)
+        {
+            return Err("assumed that the border of the input is all walkable tiles".to_string());
+        }
+    }
+    if garden.width() % 2 != 1 {
+        return Err("assumed odd width".to_string());
+    }
+    // yet another assumption: the starting point is in the exact centet, and the direct line from
+    // the starting point to the end has no rocks on it
+    if start_x != garden.width() / 2 || start_y != garden.width() / 2 {
+        return Err("assumed the starting point to be in the center".to_string());
+    }
+    if !(0..garden.width()).all(|d| {
+        garden.get(d, garden.width() / 2) == Some(&Tile::Plot)
+            && garden.get(garden.width() / 2, d) == Some(&Tile::Plot)
+    }) {
+        return Err("assumed straight lines from starting point to the edges".to_string());
+    }
+
+    // shortest paths starting at the starting point
+    let shortest_path_start = explore(start_x, start_y, garden);
+    // shortest paths when the start is in the north-west corner (and north-east, south-west and
+    // south-east respectively)
+    let shortest_path_nw: HashMap<(usize, usize), usize> = explore(0, 0, garden);
+    let shortest_path_ne: HashMap<(usize, usize), usize> = explore(garden.width() - 1, 0, garden);
+    let shortest_path_sw: HashMap<(usize, usize), usize> = explore(0, garden.width() - 1, garden);
+    let shortest_path_se: HashMap<(usize, usize), usize> =
+        explore(garden.width() - 1, garden.width() - 1, garden);
+
+    // shortest path when the start is in the middle of the northern edge (and easter, southern,
+    // western respectively)
+    let shortest_path_n: HashMap<(usize, usize), usize> = explore(garden.width() / 2, 0, garden);
+    let shortest_path_e: HashMap<(usize, usize), usize> =
+        explore(garden.width() - 1, garden.width() / 2, garden);
+    let shortest_path_s: HashMap<(usize, usize), usize> =
+        explore(garden.width() / 2, garden.width() - 1, garden);
+    let shortest_path_w: HashMap<(usize, usize), usize> = explore(0, garden.width() / 2, garden);
+
+    let corner_offset = garden.width() as u128 - 1;
+    let corner_range = (steps.saturating_sub(corner_offset + 2)) / (garden.width() as u128);
+
+    let (nw_even, nw_odd) = count_even_or_odd(&shortest_path_se, steps as usize);
+    let (ne_even, ne_odd) = count_even_or_odd(&shortest_path_sw, steps as usize);
+    let (sw_even, sw_odd) = count_even_or_odd(&shortest_path_ne, steps as usize);
+    let (se_even, se_odd) = count_even_or_odd(&shortest_path_nw, steps as usize);
+    let (n_even, n_odd) = count_even_or_odd(&shortest_path_s, steps as usize);
+    let (e_even, e_odd) = count_even_or_odd(&shortest_path_w, steps as usize);
+    let (s_even, s_odd) = count_even_or_odd(&shortest_path_n, steps as usize);
+    let (w_even, w_odd) = count_even_or_odd(&shortest_path_e, steps as usize);
+
+    let garden_range_straight =
+        steps.saturating_sub(garden.width() as u128 / 2 + 1) / garden.width() as u128;
+    let straight_steps = steps.saturating_sub(
+        garden.width() as u128 / 2 + 1 + garden.width() as u128 * garden_range_straight,
+    );
+    let straight_partial = if straight_steps > 0 && straight_steps.is_multiple_of(2) {
+        count_even_or_odd(&shortest_path_s, straight_steps as usize).0
+            + count_even_or_odd(&shortest_path_w, straight_steps as usize).0
+            + count_even_or_odd(&shortest_path_n, straight_steps as usize).0
+            + count_even_or_odd(&shortest_path_e, straight_steps as usize).0
+    } else if straight_steps > 0 {
+        count_even_or_odd(&shortest_path_s, straight_steps as usize).1
+            + count_even_or_odd(&shortest_path_w, straight_steps as usize).1
+            + count_even_or_odd(&shortest_path_n, straight_steps as usize).1
+            + count_even_or_odd(&shortest_path_e, straight_steps as usize).1
+    } else if garden_range_straight * garden.width() as u128 + garden.width() as u128 / 2 < steps {
+        4
+    } else {
+        0
+    };
+
+    let (start_even, start_odd) = count_even_or_odd(&shortest_path_start, steps as usize);
+    // possibilities in the starting garden
+    Ok(if steps.is_multiple_of(2) {
+        start_even
+    } else {
+        start_odd
+    } +
+    // possibilities in the triangles (north-east, north-west, south-east, south-west)
+    corner_full(corner_range, nw_even, nw_odd, steps)
+        + corner_part(corner_range, garden.width() as u128, corner_offset, steps, &shortest_path_se) +
+    corner_full(corner_range, ne_even, ne_odd, steps)
+        + corner_part(corner_range, garden.width() as u128, corner_offset, steps, &shortest_path_sw) +
+    corner_full(corner_range, sw_even, sw_odd, steps)
+        + corner_part(corner_range, garden.width() as u128, corner_offset, steps, &shortest_path_ne) +
+    corner_full(corner_range, se_even, se_odd, steps)
+        + corner_part(corner_range, garden.width() as u128, corner_offset, steps, &shortest_path_nw) +
+        if steps.is_multiple_of(2) {
+    (n_even + e_even + s_even + w_even) * (garden_range_straight/2 + garden_range_straight%2) + (n_odd + e_odd + s_odd + w_odd) * (garden_range_straight/2) + straight_partial
+        } else {
+    (n_odd + e_odd + s_odd + w_odd) * (garden_range_straight/2 + garden_range_straight%2) + (n_even + e_even + s_even + w_even) * (garden_range_straight/2) + straight_partial
+        })
+}
+
+fn corner_full(range: u128, even: u128, odd: u128, total_steps: u128) -> u128 {
+    let range = range.saturating_sub(1);
+    let n_total = ((range + 1) * range) / 2;
+    let n_odd = (range / 2 + 1) * (range / 2);
+    let n_even = n_total - n_odd;
+    if total_steps.is_multiple_of(2) {
+        n_even * even + n_odd * odd
+    } else {
+        n_even * odd + n_odd * even
+    }
+}
+
+fn corner_part(
+    range: u128,
+    width: u128,
+    offset: u128,
+    total_steps: u128,
+    distances: &HashMap<(usize, usize), usize>,
+) -> u128 {
+    if total_steps <= range * width + offset + 1 {
+        return 0;
+    }
+    ({
+        let remaining_steps = total_steps.saturating_sub(range * width + offset + 2) as usize;
+        let (even, odd) = count_even_or_odd(distances, remaining_steps);
+        (if remaining_steps.is_multiple_of(2) {
+            even
+        } else {
+            odd
+        } * (range + 1))
+    } + if range > 0 {
+        let range = range - 1;
+        let remaining_steps = total_steps.saturating_sub(range * width + offset + 2) as usize;
+        let (even, odd) = count_even_or_odd(distances, remaining_steps);
+        (if remaining_steps.is_multiple_of(2) {
+            even
+        } else {
+            odd
+        } * (range + 1))
+    } else {
+        0
+    })
+}
+
+fn count_even_or_odd(distances: &HashMap<(usize, usize), usize>, steps: usize) -> (u128, u128) {
+    let mut even = 0;
+    let mut odd = 0;
+    for d in distances.values() {
+        if *d <= steps {
+            if d % 2 == 0 {
+                even += 1
+            } else {
+                odd += 1
+            }
+        }
+    }
+    (even, odd)
+}
+
+/// The number of garden plots that can be reached in exactly `steps` steps.
+pub fn possible_plots(start_x: usize, start_y: usize, garden: &Garden, steps: usize) -> u128 {
+    let (even, odd) = count_even_or_odd(&explore(start_x, start_y, garden), steps);
+    if steps.is_multiple_of(2) {
+        even
+    } else {
+        odd
+    }
+}
+
+/// The minimal number of steps to every reachable garden plot.
+pub fn explore(start_x: usize, start_y: usize, garden: &Garden) -> HashMap<(usize, usize), usize> {
+    let mut queue: VecDeque<(usize, usize, usize)> = VecDeque::with_capacity(garden.len() * 4);
+    let mut visited: HashMap<(usize, usize), usize> = HashMap::with_capacity(garden.len());
+
+    queue.push_back((start_x, start_y, 0));
+    while let Some((x, y, steps)) = queue.pop_front() {
+        if visited.contains_key(&(x, y)) {
+            continue;
+        }
+        visited.insert((x, y), steps);
+        for (x, y) in garden.neighbours4(x, y) {
+            if garden[(x, y)] == Tile::Plot {
+                queue.push_back((x, y, steps + 1));
+            }
+        }
+    }
+
+    visited
+}
+
+/// Parses the garden and the position of the start, which is a garden plot.
+pub fn parse(input: &str) -> Result<(usize, usize, Garden), ParseError> {
+    let garden = Grid::parse(input, |c| match c {
+        '.' | 'S' => Ok(Tile::Plot),
+        '#' => Ok(Tile::Rock),
+        _ => Err(ErrorKind::Unknown("tile")),
+    })?;
+
+    let start_i = input
+        .chars()
+        .filter(|c| *c != '\n')
+        .position(|c| c == 'S')
+        .ok_or_else(|| {
+            ParseError::at(
+                input,
+                &input[..0],
+                ErrorKind::Missing("starting position 'S'"),
+            )
+        })?;
+
+    Ok((start_i % garden.width(), start_i / garden.width(), garden))
+}
+
+/// A tile of the garden: a garden plot or a rock.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Tile {
+    Plot,
+    Rock,
+}
+
+/// A garden of plots and rocks.
+pub type Garden = Grid<Tile>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = r#"...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........
+"#;
+
+    #[test]
+    fn possible_plots_works_for_example() {
+        // given
+        let (sx, sy, garden) = parse(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let n = possible_plots(sx, sy, &garden, 6);
+
+        // then
+        assert_eq!(n, 16);
+    }
+
+    #[test]
+    fn possible_plots_on_infinite_garden_works_same_as_possible_plots_with_expanded_empty_area() {
+        // given
+        let mini = Garden::filled(3, 3, Tile::Plot);
+        let expanded = Garden::filled(21, 21, Tile::Plot);
+
+        for steps in 1..=10 {
+            println!("steps: {steps}");
+            // when
+            let n_infinite = possible_plots_on_infinite_garden(1, 1, &mini, steps as u128);
+            let n_expanded = possible_plots(10, 10, &expanded, steps);
+
+            // then
+            assert_eq!(n_infinite, Ok(n_expanded));
+        }
+    }
+
+    #[test]
+    fn possible_plots_on_infinite_garden_works_same_as_possible_plots_with_expanded_rocky_area() {
+        // given
+        let mut mini = Garden::filled(5, 5, Tile::Plot);
+        mini.set(3, 3, Tile::Rock);
+        let expanded = Garden::from_fn(25, 25, |x, y| mini[(x % 5, y % 5)]);
+
+        for steps in 1..=12 {
+            println!("steps: {steps}");
+            // when
+            let n_infinite = possible_plots_on_infinite_garden(2, 2, &mini, steps as u128);
+            let n_expanded = possible_plots(12, 12, &expanded, steps);
+
+            // then
+            assert_eq!(n_infinite, Ok(n_expanded));
+        }
+    }
+}
@@ -0,0 +1,212 @@
+//! Day 22: Sand Slabs
+//!
+//! Falling bricks of sand, and what happens if one of them is removed.
+
+use common::{parse_lines, parse_number, split_once, Answer, ErrorKind, ParseError, Solution};
+use std::collections::{HashMap, HashSet};
+
+/// Solves both parts of the puzzle, see [`Solution`].
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 22;
+
+    type Input<'a> = Vec<Brick>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Answer {
+        safely_disintegratable(input).into()
+    }
+
+    fn part_two(input: &Self::Input<'_>) -> Answer {
+        sum_chain_reaction(input).into()
+    }
+}
+
+/// The number of bricks that can be removed without other bricks falling down.
+///
+/// Preconditions: bricks must be sorted by lowest z.
+pub fn safely_disintegratable(bricks: &[Brick]) -> usize {
+    let supported_by = find_supported_by(bricks);
+    let mut removable = vec![true; bricks.len()];
+    for supports in supported_by.values() {
+        if supports.len() == 1 {
+            removable[supports[0]] = false;
+        }
+    }
+    removable.iter().filter(|r| **r).count()
+}
+
+fn find_supported_by(bricks: &[Brick]) -> HashMap<usize, Vec<usize>> {
+    let settled = settle_bricks(bricks);
+    let mut supported_by: HashMap<usize, Vec<usize>> = HashMap::with_capacity(bricks.len());
+    for (i, brick) in settled.iter().rev().enumerate() {
+        for (j, possible_support) in settled.iter().rev().enumerate().skip(i + 1) {
+            if brick.from.2 == possible_support.to.2 + 1 && overlap_xy(brick, possible_support) {
+                supported_by
+                    .entry(i)
+                    .or_insert(Vec::with_capacity(8))
+                    .push(j);
+            }
+        }
+    }
+    supported_by
+}
+
+/// Sums up the numbers of other bricks that would fall when removing each brick.
+pub fn sum_chain_reaction(bricks: &[Brick]) -> usize {
+    let supported_by = find_supported_by(bricks);
+    println!(
+        "supported bricks: {}, total: {}",
+        supported_by.len(),
+        bricks.len()
+    );
+
+    (0..bricks.len())
+        .map(|brick_i| chain_reaction(brick_i, &supported_by))
+        .sum()
+}
+
+fn chain_reaction(removed_brick: usize, supported_by: &HashMap<usize, Vec<usize>>) -> usize {
+    let mut fallen: HashSet<usize> = HashSet::with_capacity(supported_by.len());
+    fallen.insert(removed_brick);
+
+    let mut can_fall: HashSet<usize> = supported_by.keys().copied().collect();
+    let mut newly_fallen: Vec<usize> = Vec::with_capacity(can_fall.len());
+    newly_fallen.push(removed_brick);
+    // there is probably a more efficient way than to go over all the bricks over and over again,
+    // but it should still work
+    while !newly_fallen.is_empty() {
+        for brick_i in newly_fallen.drain(..) {
+            can_fall.remove(&brick_i);
+        }
+        for candidate in &can_fall {
+            if fallen.contains(candidate) {
+                continue;
+            }
+            if supported_by
+                .get(candidate)
+                .map(|supports| supports.iter().all(|support| fallen.contains(support)))
+                .unwrap_or(false)
+            {
+                fallen.insert(*candidate);
+                newly_fallen.push(*candidate);
+            }
+        }
+    }
+
+    fallen.len() - 1
+}
+
+/// Lets all bricks fall down as far as they can. The result is sorted by lowest z.
+pub fn settle_bricks(bricks: &[Brick]) -> Vec<Brick> {
+    let mut settled = Vec::with_capacity(bricks.len());
+    for brick in bricks {
+        let mut next = brick.clone();
+        next.to.2 = next.to.2 + 1 - next.from.2;
+        next.from.2 = 1;
+        for s in settled.iter().rev() {
+            if overlap_xy(s, &next) {
+                next.to.2 -= next.from.2;
+                next.from.2 = s.to.2 + 1;
+                next.to.2 += next.from.2;
+                break;
+            }
+        }
+        settled.push(next);
+        // TODO: inefficient, but for now it works fast enough
+        settled.sort_by_key(|brick| brick.to.2);
+    }
+    settled
+}
+
+fn overlap_xy(b1: &Brick, b2: &Brick) -> bool {
+    (b1.from.0 <= b2.from.0 && b1.to.0 >= b2.from.0
+        || b1.from.0 >= b2.from.0 && b1.from.0 <= b2.to.0)
+        && (b1.from.1 <= b2.from.1 && b1.to.1 >= b2.from.1
+            || b1.from.1 >= b2.from.1 && b1.from.1 <= b2.to.1)
+}
+
+/// A brick, given by two opposite corners.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Brick {
+    pub from: (u32, u32, u32),
+    pub to: (u32, u32, u32),
+}
+
+/// Parses one brick per line.
+pub fn parse(input: &str) -> Result<Vec<Brick>, ParseError> {
+    let mut bricks: Vec<Brick> = parse_lines(input, parse_brick)?;
+    bricks.sort_by_key(|b| b.from.2);
+    Ok(bricks)
+}
+
+/// Parses a brick like `1,0,1~1,2,1`.
+pub fn parse_brick(line: &str) -> Result<Brick, ParseError> {
+    let (from_str, to_str) = split_once(line, line, "~", "'~' between the brick ends")?;
+    let from = parse_coords(line, from_str)?;
+    let to = parse_coords(line, to_str)?;
+    if from.0 > to.0 || from.1 > to.1 || from.2 > to.2 {
+        // the inputs are ordered this way. If they are not, we could order them here instead of
+        // returning an error
+        let kind = ErrorKind::Invalid("to-values are higher than from-values".to_owned());
+        Err(ParseError::at(line, to_str, kind))
+    } else {
+        Ok(Brick { from, to })
+    }
+}
+
+fn parse_coords(line: &str, s: &str) -> Result<(u32, u32, u32), ParseError> {
+    let mut coords = s.splitn(3, ',').map(|part| parse_number(line, part));
+    let mut next = || {
+        coords.next().unwrap_or_else(|| {
+            Err(ParseError::at(
+                line,
+                s,
+                ErrorKind::Missing("third coordinate"),
+            ))
+        })
+    };
+    Ok((next()?, next()?, next()?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = r#"1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9
+"#;
+
+    #[test]
+    fn safely_disintegratable_works_for_example() {
+        // given
+        let bricks = parse(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let n = safely_disintegratable(&bricks);
+
+        // then
+        assert_eq!(n, 5);
+    }
+
+    #[test]
+    fn sum_chain_reaction_works_for_example() {
+        // given
+        let bricks = parse(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let n = sum_chain_reaction(&bricks);
+
+        // then
+        assert_eq!(n, 7);
+    }
+}
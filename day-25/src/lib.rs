@@ -0,0 +1,143 @@
+//! Day 25: Snowverload
+//!
+//! A network of components that falls apart into two groups when cutting three wires.
+
+use common::{split_once, Answer, ParseError, Solution};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Solves both parts of the puzzle, see [`Solution`].
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    const DAY: u8 = 25;
+
+    type Input<'a> = HashMap<&'a str, Vec<&'a str>>;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        parse(input)
+    }
+
+    fn part_one(input: &Self::Input<'_>) -> Answer {
+        match find_cut_3(input) {
+            Some(n) => n.into(),
+            None => Answer::unsolved(
+                "Could not find a way to split the components by removing only three connections.",
+            ),
+        }
+    }
+
+    fn part_two(_: &Self::Input<'_>) -> Answer {
+        Answer::unsolved("There is no second part on the last day")
+    }
+}
+
+/// The product of the sizes of the two groups that are left after cutting three wires.
+pub fn find_cut_3(connections: &HashMap<&str, Vec<&str>>) -> Option<usize> {
+    let from = connections.keys().next()?;
+    for to in connections.keys().filter(|to| to != &from) {
+        let (cut, from_size) = min_cut(from, to, connections);
+        if cut <= 3 {
+            return Some(from_size * (connections.len() - from_size));
+        }
+    }
+    None
+}
+
+/// Searches for a minimal cut between `from` and `to`, and returns the size of the cut and the
+/// size of the part that `from` belongs to.
+pub fn min_cut(from: &str, to: &str, connections: &HashMap<&str, Vec<&str>>) -> (u32, usize) {
+    // simplified Ford & Fulkerson, since we have unit capacities
+    // I did not check this for correctness, so it may not work for all inputs
+    let mut used_connections: HashSet<(&str, &str)> = HashSet::with_capacity(connections.len() * 6);
+
+    let mut flow: u32 = 0;
+    let mut found_path = true;
+    let mut queue: VecDeque<(&str, Option<&str>)> = VecDeque::with_capacity(connections.len());
+    let mut visited: HashMap<&str, Option<&str>> = HashMap::with_capacity(connections.len());
+    while found_path {
+        found_path = false;
+        queue.clear();
+        visited.clear();
+        queue.push_back((from, None));
+
+        while let Some((v, pred)) = queue.pop_front() {
+            if visited.contains_key(&v) {
+                continue;
+            }
+            visited.insert(v, pred);
+            if v == to {
+                break;
+            }
+            for neighbour in connections.get(&v).iter().flat_map(|c| c.iter()) {
+                if !used_connections.contains(&(v, neighbour)) {
+                    queue.push_back((neighbour, Some(v)));
+                }
+            }
+        }
+
+        if visited.contains_key(&to) {
+            found_path = true;
+            flow += 1;
+        }
+        let mut backtrack = to;
+        while let Some(pred) = visited.get(&backtrack).copied().flatten() {
+            used_connections.insert((pred, backtrack));
+            used_connections.insert((backtrack, pred));
+            backtrack = pred;
+        }
+    }
+    (flow, visited.len())
+}
+
+/// Parses the connections between components, in both directions.
+pub fn parse(input: &str) -> Result<HashMap<&str, Vec<&str>>, ParseError> {
+    let mut connections = HashMap::with_capacity(2048);
+
+    for line in input.lines() {
+        let (from, to) = split_once(input, line, ": ", "': ' after the component")?;
+        for to in to.split_whitespace() {
+            connections
+                .entry(from)
+                .or_insert(Vec::with_capacity(8))
+                .push(to);
+            connections
+                .entry(to)
+                .or_insert(Vec::with_capacity(8))
+                .push(from);
+        }
+    }
+
+    Ok(connections)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = r#"jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr
+"#;
+
+    #[test]
+    fn find_cut_3_works_for_example() {
+        // given
+        let connections = parse(EXAMPLE).expect("expected successful parsing");
+
+        // when
+        let n = find_cut_3(&connections);
+
+        // then
+        assert_eq!(n, Some(54));
+    }
+}
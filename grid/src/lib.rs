@@ -0,0 +1,395 @@
+//! A rectangular grid shared by the days whose puzzle input is a map of characters.
+
+use common::{ErrorKind, ParseError};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// A rectangular grid, stored row by row. x is the column, y is the row, (0, 0) is the top left
+/// corner.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells, row by row. Fails if their number does not match the size.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Result<Self, String> {
+        if width.checked_mul(height) != Some(cells.len()) {
+            return Err(format!(
+                "a grid of size {width}x{height} can not have {} cells",
+                cells.len()
+            ));
+        }
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Creates a grid with the value of `f(x, y)` in every cell.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses one cell per character, one row per line. All lines must have the same length.
+    pub fn parse(
+        input: &str,
+        mut parse_cell: impl FnMut(char) -> Result<T, ErrorKind>,
+    ) -> Result<Self, ParseError> {
+        let mut width: Option<usize> = None;
+        let mut height: usize = 0;
+        let mut cells: Vec<T> = Vec::with_capacity(input.len());
+        for (y, line) in input.lines().enumerate() {
+            let before = cells.len();
+            for (i, c) in line.char_indices() {
+                let cell = parse_cell(c)
+                    .map_err(|kind| ParseError::at(line, &line[i..i + c.len_utf8()], kind))
+                    .map_err(|e| e.on_line(y + 1))?;
+                cells.push(cell);
+            }
+            let line_width = cells.len() - before;
+            match width {
+                None => width = Some(line_width),
+                Some(width) if width != line_width => {
+                    let kind = ErrorKind::Invalid(format!(
+                        "expected {width} tiles like in the first line, found {line_width}"
+                    ));
+                    return Err(ParseError::at(line, line, kind).on_line(y + 1));
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        match width {
+            Some(width) if width > 0 => Ok(Grid {
+                width,
+                height,
+                cells,
+            }),
+            _ => Err(ParseError::at(input, input, ErrorKind::Missing("grid"))),
+        }
+    }
+
+    /// The number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The number of cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns true if the grid has no cells.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Returns true if the position is inside of the grid.
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// The cell at the given position, if it is inside of the grid.
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if self.contains(x, y) {
+            self.cells.get(x + y * self.width)
+        } else {
+            None
+        }
+    }
+
+    /// Like [`Grid::get`], but mutable.
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if self.contains(x, y) {
+            self.cells.get_mut(x + y * self.width)
+        } else {
+            None
+        }
+    }
+
+    /// Replaces the cell at the given position. Returns false if it is outside of the grid.
+    pub fn set(&mut self, x: usize, y: usize, value: T) -> bool {
+        if let Some(cell) = self.get_mut(x, y) {
+            *cell = value;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Like [`Grid::get`], but treats the grid as if it was repeated infinitely in all directions.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> Option<&T> {
+        if self.is_empty() {
+            return None;
+        }
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        self.get(x, y)
+    }
+
+    /// The positions north, east, south and west of the given one, if they are in the grid.
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .into_iter()
+            .filter_map(move |(dx, dy)| self.offset(x, y, dx, dy))
+    }
+
+    /// Like [`Grid::neighbours4`], but includes diagonal neighbours, clockwise starting in the
+    /// north.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        [
+            (0, -1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+            (0, 1),
+            (-1, 1),
+            (-1, 0),
+            (-1, -1),
+        ]
+        .into_iter()
+        .filter_map(move |(dx, dy)| self.offset(x, y, dx, dy))
+    }
+
+    fn offset(&self, x: usize, y: usize, dx: isize, dy: isize) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        if self.contains(x, y) {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+
+    /// The cells of row `y`, if it is inside of the grid.
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        if y < self.height {
+            Some(&self.cells[y * self.width..(y + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    /// All rows, from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    /// The cells of column `x` from top to bottom, none if it is outside of the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let width = self.width.max(1);
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(width)
+            .take(if x < self.width { self.height } else { 0 })
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The position of the first cell, row by row, that matches the predicate.
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|i| (i % self.width, i / self.width))
+    }
+
+    /// A grid of the same size with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with `value` in every cell.
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Mirrors the grid along the diagonal from the top left to the bottom right.
+    pub fn transpose(&self) -> Self {
+        Grid::from_fn(self.height, self.width, |x, y| self[(y, x)].clone())
+    }
+
+    /// Rotates the grid by a quarter turn clockwise.
+    pub fn rotate_clockwise(&self) -> Self {
+        Grid::from_fn(self.height, self.width, |x, y| {
+            self[(y, self.height - 1 - x)].clone()
+        })
+    }
+
+    /// Rotates the grid by a quarter turn counter clockwise.
+    pub fn rotate_counter_clockwise(&self) -> Self {
+        Grid::from_fn(self.height, self.width, |x, y| {
+            self[(self.width - 1 - y, x)].clone()
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("position ({x}, {y}) is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("position ({x}, {y}) is outside of the grid"))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EXAMPLE: &str = r#"abc
+def
+"#;
+
+    fn example() -> Grid<char> {
+        Grid::parse(EXAMPLE, Ok).expect("expected successful parsing")
+    }
+
+    #[test]
+    fn parse_works_for_example() {
+        // when
+        let grid = example();
+
+        // then
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.get(0, 0), Some(&'a'));
+        assert_eq!(grid.get(2, 1), Some(&'f'));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+    }
+
+    #[test]
+    fn parse_fails_for_invalid_input() {
+        assert!(Grid::parse("abc\nde\n", Ok).is_err());
+        assert!(Grid::parse("", Ok).is_err());
+
+        let error = Grid::parse("aa\nab\n", |c| {
+            if c == 'a' {
+                Ok(c)
+            } else {
+                Err(ErrorKind::Unknown("cell"))
+            }
+        })
+        .expect_err("expected parsing to fail");
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.text, "b");
+    }
+
+    #[test]
+    fn get_wrapping_works() {
+        // given
+        let grid = example();
+
+        // then
+        assert_eq!(grid.get_wrapping(3, 0), Some(&'a'));
+        assert_eq!(grid.get_wrapping(-1, -1), Some(&'f'));
+        assert_eq!(grid.get_wrapping(-4, 5), Some(&'f'));
+    }
+
+    #[test]
+    fn neighbours_work() {
+        // given
+        let grid = example();
+
+        // when
+        let n4: Vec<(usize, usize)> = grid.neighbours4(0, 0).collect();
+        let n8: Vec<(usize, usize)> = grid.neighbours8(1, 1).collect();
+
+        // then
+        assert_eq!(n4, vec![(1, 0), (0, 1)]);
+        assert_eq!(n8, vec![(1, 0), (2, 0), (2, 1), (0, 1), (0, 0)]);
+    }
+
+    #[test]
+    fn rows_and_columns_work() {
+        // given
+        let grid = example();
+
+        // then
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.column(3).count(), 0);
+    }
+
+    #[test]
+    fn transpose_and_rotate_work() {
+        // given
+        let grid = example();
+
+        // then
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_counter_clockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(
+            grid.rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise(),
+            grid
+        );
+    }
+
+    #[test]
+    fn display_works() {
+        assert_eq!(example().to_string(), EXAMPLE);
+    }
+}